@@ -46,7 +46,14 @@ impl App {
 
 impl Game for App {
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{}: {} - FPS: {}", TITLE, self.bunnies.len(), engine.timer().real_time_fps().round());
+        if engine.keyboard().is_key_down(KeyCode::Space) {
+            let sprite_instancing = engine.graphics().sprite_instancing();
+            engine.graphics().set_sprite_instancing(!sprite_instancing);
+        }
+
+        let mode = if engine.graphics().sprite_instancing() { "Instanced" } else { "Batched" };
+        let draw_calls = engine.graphics().stats().draw_calls;
+        let title = format!("{}: {} ({}, {} draw calls) - FPS: {}", TITLE, self.bunnies.len(), mode, draw_calls, engine.timer().real_time_fps().round());
         engine.window().set_title(title);
 
         let max_position = {
@@ -106,6 +113,10 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .graphics_config(GraphicsConfig::new()
+            .sprite_instancing(true))
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/bunny.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1024.0, 600.0)))
@@ -241,6 +241,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/ark-pixel-font/ark-pixel-12px-zh_cn.otf", "assets/car.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1280.0, 640.0)))
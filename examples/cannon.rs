@@ -192,6 +192,12 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files![
+                "assets/ark-pixel-font/ark-pixel-12px-zh_cn.otf",
+                "assets/battery.png",
+                "assets/bullet.png",
+            ])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((640.0, 640.0))
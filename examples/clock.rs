@@ -88,6 +88,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/clock-disk.png"])))
         .window_config(WindowConfig::new()
             .inner_size((600.0, 600.0))
             .resizable(false))
@@ -90,6 +90,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/dvd-logo.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1024.0, 600.0))
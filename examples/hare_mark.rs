@@ -63,7 +63,13 @@ impl App {
 
 impl Game for App {
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{}: {} - FPS: {}", TITLE, self.hares.len(), engine.timer().real_time_fps().round());
+        if engine.keyboard().is_key_down(KeyCode::Space) {
+            let sprite_instancing = engine.graphics().sprite_instancing();
+            engine.graphics().set_sprite_instancing(!sprite_instancing);
+        }
+
+        let mode = if engine.graphics().sprite_instancing() { "Instanced" } else { "Batched" };
+        let title = format!("{}: {} ({}) - FPS: {}", TITLE, self.hares.len(), mode, engine.timer().real_time_fps().round());
         engine.window().set_title(title);
 
         let delta_time_f32 = engine.timer().delta_time().as_secs_f32();
@@ -127,6 +133,10 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .graphics_config(GraphicsConfig::new()
+            .sprite_instancing(true))
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/hare.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1024.0, 600.0)))
@@ -51,6 +51,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/ferris.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((600.0, 700.0)))
@@ -4,14 +4,27 @@ const TITLE: &str = "Mesh";
 
 struct App {
     texture: Texture,
+    quad: Mesh,
     angle: Angle,
 }
 
 impl App {
     fn new(engine: &mut Engine) -> GameResult<Self> {
         let texture = Texture::load(engine, "assets/sky.png")?;
+        let quad = Mesh::new(
+            engine.graphics(),
+            PrimitiveType::Triangles,
+            &[
+                Vertex::new((-50.0, -50.0), (0.0, 0.0), Color::WHITE),
+                Vertex::new((50.0, -50.0), (1.0, 0.0), Color::WHITE),
+                Vertex::new((-50.0, 50.0), (0.0, 1.0), Color::RED),
+                Vertex::new((50.0, 50.0), (1.0, 1.0), Color::RED),
+            ],
+            Some(Elements::U16(vec![0, 2, 1, 1, 2, 3])),
+        )?;
         Ok(Self {
             texture,
+            quad,
             angle: Angle::zero(),
         })
     }
@@ -57,12 +70,25 @@ impl Game for App {
                 .translate((400.0, 300.0)),
         );
 
+        for x in [150.0, 874.0] {
+            engine.graphics().draw_static_mesh(
+                &self.texture,
+                &self.quad,
+                None,
+                Transform::default()
+                    .rotate(self.angle * -1.0)
+                    .translate((x, 300.0)),
+            );
+        }
+
         Ok(())
     }
 }
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/sky.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1024.0, 600.0)))
@@ -126,6 +126,13 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files![
+                "assets/parallax-forest/back-trees.png",
+                "assets/parallax-forest/lights.png",
+                "assets/parallax-forest/middle-trees.png",
+                "assets/parallax-forest/front-trees.png",
+            ])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((272.0 * 2.0, 160.0 * 2.0)))
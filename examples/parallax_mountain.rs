@@ -134,6 +134,14 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files![
+                "assets/parallax-mountain/background.png",
+                "assets/parallax-mountain/mountain-far.png",
+                "assets/parallax-mountain/mountains.png",
+                "assets/parallax-mountain/trees.png",
+                "assets/parallax-mountain/foreground-trees.png",
+            ])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((272.0 * 2.0, 160.0 * 2.0)))
@@ -78,6 +78,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .mount(Mount::embedded(tge::embed_files!["assets/sky.png"])))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((320.0 * 2.0, 256.0 * 2.0)))
@@ -0,0 +1,172 @@
+use crate::error::GameResult;
+use crate::engine::Engine;
+use crate::filesystem::normalize_path;
+use crate::graphics::{Image, Texture, Font, Program};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::Path;
+use std::rc::Rc;
+
+struct Cache<K, T> {
+    entries: HashMap<K, Rc<T>>,
+}
+
+impl<K: Hash + Eq, T> Cache<K, T> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<Rc<T>> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, asset: T) -> Rc<T> {
+        let asset = Rc::new(asset);
+        self.entries.insert(key, asset.clone());
+        asset
+    }
+
+    fn use_count(&self, matches: impl Fn(&K) -> bool) -> Option<usize> {
+        self.entries.iter()
+            .filter(|(key, _)| matches(key))
+            .map(|(_, asset)| Rc::strong_count(asset) - 1)
+            .max()
+    }
+
+    fn unload(&mut self, matches: impl Fn(&K) -> bool) -> usize {
+        let count = self.entries.len();
+        self.entries.retain(|key, _| !matches(key));
+        count - self.entries.len()
+    }
+
+    fn unload_unused(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.retain(|_, asset| Rc::strong_count(asset) > 1);
+        count - self.entries.len()
+    }
+}
+
+fn asset_key(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    normalize_path(&path).unwrap_or_else(|| path.into_owned())
+}
+
+pub struct Assets {
+    images: Cache<String, Image>,
+    textures: Cache<String, Texture>,
+    fonts: Cache<String, Font>,
+    programs: Cache<(String, String), Program>,
+}
+
+impl Assets {
+    pub(crate) fn new() -> Self {
+        Self {
+            images: Cache::new(),
+            textures: Cache::new(),
+            fonts: Cache::new(),
+            programs: Cache::new(),
+        }
+    }
+
+    pub fn load_image(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Rc<Image>> {
+        let path = path.as_ref();
+        let key = asset_key(path);
+        if let Some(image) = engine.assets().images.get(&key) {
+            return Ok(image);
+        }
+        let image = Image::load(engine, path)?;
+        Ok(engine.assets().images.insert(key, image))
+    }
+
+    pub fn load_texture(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Rc<Texture>> {
+        let path = path.as_ref();
+        let key = asset_key(path);
+        if let Some(texture) = engine.assets().textures.get(&key) {
+            return Ok(texture);
+        }
+        let texture = Texture::load(engine, path)?;
+        Ok(engine.assets().textures.insert(key, texture))
+    }
+
+    pub fn load_font(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Rc<Font>> {
+        let path = path.as_ref();
+        let key = asset_key(path);
+        if let Some(font) = engine.assets().fonts.get(&key) {
+            return Ok(font);
+        }
+        let font = Font::load(engine, path)?;
+        Ok(engine.assets().fonts.insert(key, font))
+    }
+
+    pub fn load_program(engine: &mut Engine, vertex_shader_path: impl AsRef<Path>, fragment_shader_path: impl AsRef<Path>) -> GameResult<Rc<Program>> {
+        let vertex_shader_path = vertex_shader_path.as_ref();
+        let fragment_shader_path = fragment_shader_path.as_ref();
+        let key = (asset_key(vertex_shader_path), asset_key(fragment_shader_path));
+        if let Some(program) = engine.assets().programs.get(&key) {
+            return Ok(program);
+        }
+        let program = Program::load(engine, vertex_shader_path, fragment_shader_path)?;
+        Ok(engine.assets().programs.insert(key, program))
+    }
+
+    pub fn is_loaded(&self, path: impl AsRef<Path>) -> bool {
+        self.use_count(path).is_some()
+    }
+
+    pub fn use_count(&self, path: impl AsRef<Path>) -> Option<usize> {
+        let key = asset_key(path.as_ref());
+        [
+            self.images.use_count(|image_key| *image_key == key),
+            self.textures.use_count(|texture_key| *texture_key == key),
+            self.fonts.use_count(|font_key| *font_key == key),
+            self.programs.use_count(|(vertex_shader_key, fragment_shader_key)| *vertex_shader_key == key || *fragment_shader_key == key),
+        ].into_iter().flatten().max()
+    }
+
+    pub fn unload(&mut self, path: impl AsRef<Path>) -> usize {
+        let key = asset_key(path.as_ref());
+        self.images.unload(|image_key| *image_key == key)
+            + self.textures.unload(|texture_key| *texture_key == key)
+            + self.fonts.unload(|font_key| *font_key == key)
+            + self.programs.unload(|(vertex_shader_key, fragment_shader_key)| *vertex_shader_key == key || *fragment_shader_key == key)
+    }
+
+    pub fn unload_unused(&mut self) -> usize {
+        self.images.unload_unused()
+            + self.textures.unload_unused()
+            + self.fonts.unload_unused()
+            + self.programs.unload_unused()
+    }
+
+    pub fn clear(&mut self) {
+        self.images.entries.clear();
+        self.textures.entries.clear();
+        self.fonts.entries.clear();
+        self.programs.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, asset_key};
+    use std::path::Path;
+
+    #[test]
+    fn test_cache() {
+        let mut cache = Cache::new();
+        let key = asset_key(Path::new("assets/./bunny.png"));
+        assert_eq!(key, "assets/bunny.png");
+        let bunny = cache.insert(key.clone(), 1);
+        cache.insert("assets/cat.png".to_owned(), 2);
+        assert!(std::rc::Rc::ptr_eq(&bunny, &cache.get(&key).unwrap()));
+        assert_eq!(cache.use_count(|entry_key| *entry_key == key), Some(1));
+        assert_eq!(cache.unload_unused(), 1);
+        assert!(cache.get(&key).is_some());
+        drop(bunny);
+        assert_eq!(cache.use_count(|entry_key| *entry_key == key), Some(0));
+        assert_eq!(cache.unload(|entry_key| *entry_key == key), 1);
+        assert!(cache.get(&key).is_none());
+    }
+}
@@ -1,22 +1,276 @@
-use crate::error::GameResult;
+mod bus;
+mod mixer;
+mod output;
+mod decoder;
+mod sound;
+mod music;
+mod sound_ref;
+mod instance;
+mod spatial;
 
-pub struct Audio {}
+use mixer::Mixer;
+use output::Output;
+
+pub use bus::{MASTER_BUS, MUSIC_BUS, SFX_BUS, VOICE_BUS};
+pub use output::AudioBackend;
+pub use sound::Sound;
+pub use music::Music;
+pub use sound_ref::SoundRef;
+pub use instance::{SoundState, SoundInstance};
+pub use spatial::{DistanceModel, Attenuation};
+
+use crate::error::{GameError, GameResult};
+use crate::math::{Position, Angle};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+pub struct Audio {
+    mixer: Arc<Mutex<Mixer>>,
+    output: Output,
+    sample_rate: u32,
+    channels: u16,
+}
 
 impl Audio {
-    pub(crate) fn new(_: AudioConfig) -> GameResult<Self> {
-        Ok(Self {})
+    /// The engine creates its own, an `AudioBackend::Offline` one can also be created directly,
+    /// for rendering without a window or a sound card.
+    pub fn new(audio_config: AudioConfig) -> GameResult<Self> {
+        if audio_config.channels == 0 {
+            return Err(GameError::InitError("audio channels must > 0".into()));
+        }
+        if audio_config.sample_rate == 0 {
+            return Err(GameError::InitError("audio sample rate must > 0".into()));
+        }
+        let mixer = Arc::new(Mutex::new(Mixer::new(audio_config.sample_rate, audio_config.channels)));
+        let output = Output::new(&audio_config, mixer.clone())?;
+        // The device may not support the configured format and be opened with its own.
+        let (sample_rate, channels) = {
+            let mixer = lock_mixer(&mixer);
+            (mixer.sample_rate(), mixer.channels())
+        };
+        Ok(Self {
+            mixer,
+            output,
+            sample_rate,
+            channels,
+        })
+    }
+
+    pub(crate) fn suspend(&mut self) {
+        self.output.pause();
+    }
+
+    pub(crate) fn resume(&mut self) {
+        self.output.play();
+    }
+
+    pub(crate) fn update(&mut self, delta_time: Duration) {
+        if self.backend() != AudioBackend::Offline {
+            self.mixer().update(delta_time);
+        }
+    }
+
+    fn mixer(&self) -> MutexGuard<'_, Mixer> {
+        lock_mixer(&self.mixer)
+    }
+
+    pub fn backend(&self) -> AudioBackend {
+        self.output.backend()
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn render(&mut self, frame_count: usize) -> GameResult<Vec<f32>> {
+        if self.backend() != AudioBackend::Offline {
+            return Err(GameError::StateError(format!("audio can not be rendered on backend `{:?}`", self.backend()).into()));
+        }
+        let mut samples = vec![0.0; frame_count * self.channels as usize];
+        let mut mixer = self.mixer();
+        mixer.mix(&mut samples);
+        mixer.update(Duration::from_secs_f64(frame_count as f64 / self.sample_rate as f64));
+        Ok(samples)
     }
 
-    pub(crate) fn suspend(&mut self) {}
+    pub fn play<'a>(&mut self, sound: impl Into<SoundRef<'a>>) -> SoundInstance {
+        let sound = sound.into();
+        let source = sound.source();
+        let id = self.mixer().play(source, sound.default_bus())
+            .expect("default bus must exist");
+        SoundInstance::new(self.mixer.clone(), id)
+    }
+
+    pub fn play_on_bus<'a>(&mut self, sound: impl Into<SoundRef<'a>>, bus: &str) -> GameResult<SoundInstance> {
+        let sound = sound.into();
+        let source = sound.source();
+        let id = self.mixer().play(source, bus)?;
+        Ok(SoundInstance::new(self.mixer.clone(), id))
+    }
+
+    pub fn stop_all(&mut self) {
+        self.mixer().stop_all();
+    }
+
+    pub fn listener_position(&self) -> Position {
+        self.mixer().listener().position
+    }
+
+    pub fn set_listener_position(&mut self, position: impl Into<Position>) {
+        self.mixer().listener_mut().position = position.into();
+    }
+
+    pub fn listener_angle(&self) -> Angle {
+        self.mixer().listener().angle
+    }
+
+    pub fn set_listener_angle(&mut self, angle: Angle) {
+        self.mixer().listener_mut().angle = angle;
+    }
+
+    pub fn attenuation(&self) -> Attenuation {
+        self.mixer().listener().attenuation
+    }
 
-    pub(crate) fn resume(&mut self) {}
+    pub fn set_attenuation(&mut self, attenuation: Attenuation) {
+        self.mixer().listener_mut().attenuation = attenuation;
+    }
+
+    pub fn has_bus(&self, name: &str) -> bool {
+        self.mixer().buses().contains(name)
+    }
+
+    pub fn add_bus(&mut self, name: &str, parent: &str) -> GameResult {
+        self.mixer().buses_mut().add(name, parent)
+    }
+
+    pub fn remove_bus(&mut self, name: &str) -> GameResult {
+        self.mixer().remove_bus(name)
+    }
+
+    pub fn bus_parent(&self, name: &str) -> Option<String> {
+        self.mixer().buses().parent(name).map(|parent| parent.to_owned())
+    }
+
+    pub fn bus_volume(&self, name: &str) -> Option<f32> {
+        self.mixer().buses().volume(name)
+    }
+
+    pub fn set_bus_volume(&mut self, name: &str, volume: f32) -> GameResult {
+        self.mixer().buses_mut().set_volume(name, volume)
+    }
+
+    pub fn is_bus_muted(&self, name: &str) -> Option<bool> {
+        self.mixer().buses().is_muted(name)
+    }
+
+    pub fn set_bus_muted(&mut self, name: &str, muted: bool) -> GameResult {
+        self.mixer().buses_mut().set_muted(name, muted)
+    }
+
+    pub fn is_bus_paused(&self, name: &str) -> Option<bool> {
+        self.mixer().buses().is_paused(name)
+    }
+
+    pub fn set_bus_paused(&mut self, name: &str, paused: bool) -> GameResult {
+        self.mixer().buses_mut().set_paused(name, paused)
+    }
+}
+
+pub(crate) fn lock_mixer(mixer: &Mutex<Mixer>) -> MutexGuard<'_, Mixer> {
+    mixer.lock().unwrap_or_else(|error| error.into_inner())
 }
 
 #[derive(Debug, Clone)]
-pub struct AudioConfig {}
+pub struct AudioConfig {
+    backend: AudioBackend,
+    sample_rate: u32,
+    channels: u16,
+    buffer_size: Option<u32>,
+}
 
 impl AudioConfig {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            backend: AudioBackend::Device,
+            sample_rate: 44100,
+            channels: 2,
+            buffer_size: None,
+        }
+    }
+
+    pub fn backend(mut self, backend: AudioBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn channels(mut self, channels: u16) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    pub fn buffer_size(mut self, buffer_size: Option<u32>) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Audio, AudioConfig, AudioBackend, Sound, SFX_BUS};
+    use std::time::Duration;
+
+    fn offline_audio() -> Audio {
+        Audio::new(AudioConfig::new().backend(AudioBackend::Offline).sample_rate(1000)).unwrap()
+    }
+
+    fn constant_sound(value: f32, frame_count: usize) -> Sound {
+        Sound::new(1000, 1, vec![value; frame_count]).unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let mut audio = offline_audio();
+        let instance = audio.play(&constant_sound(0.5, 4));
+        instance.set_pan(-1.0);
+        assert_eq!(audio.render(6).unwrap(), vec![
+            0.5, 0.0,
+            0.5, 0.0,
+            0.5, 0.0,
+            0.5, 0.0,
+            0.0, 0.0,
+            0.0, 0.0,
+        ]);
+    }
+
+    #[test]
+    fn test_render_fade_and_bus() {
+        let mut audio = offline_audio();
+        let instance = audio.play(&constant_sound(0.5, 1000));
+        instance.fade_in(Duration::from_secs(1));
+        // Engine updates do not move offline audio, only rendered frames do.
+        audio.update(Duration::from_millis(500));
+        assert_eq!(audio.render(500).unwrap(), vec![0.0; 1000]);
+        assert_eq!(audio.render(250).unwrap(), vec![0.25; 500]);
+        audio.set_bus_volume(SFX_BUS, 0.5).unwrap();
+        assert_eq!(audio.render(2).unwrap(), vec![0.1875; 4]);
+        audio.set_bus_paused(SFX_BUS, true).unwrap();
+        assert_eq!(audio.render(2).unwrap(), vec![0.0; 4]);
+        assert_eq!(instance.position(), Duration::from_millis(752));
+    }
+
+    #[test]
+    fn test_render_requires_offline_backend() {
+        let mut audio = Audio::new(AudioConfig::new().backend(AudioBackend::Null)).unwrap();
+        assert!(audio.render(1).is_err());
     }
 }
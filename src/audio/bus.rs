@@ -0,0 +1,173 @@
+use crate::error::{GameError, GameResult};
+use std::collections::HashMap;
+
+pub const MASTER_BUS: &str = "master";
+pub const MUSIC_BUS: &str = "music";
+pub const SFX_BUS: &str = "sfx";
+pub const VOICE_BUS: &str = "voice";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct BusState {
+    pub gain: f32,
+    pub paused: bool,
+}
+
+struct Bus {
+    parent: Option<String>,
+    volume: f32,
+    muted: bool,
+    paused: bool,
+}
+
+impl Bus {
+    fn new(parent: Option<String>) -> Self {
+        Self {
+            parent,
+            volume: 1.0,
+            muted: false,
+            paused: false,
+        }
+    }
+}
+
+pub(crate) struct Buses {
+    buses: HashMap<String, Bus>,
+    states: HashMap<String, BusState>,
+}
+
+impl Buses {
+    pub fn new() -> Self {
+        let mut buses = HashMap::new();
+        buses.insert(MASTER_BUS.to_owned(), Bus::new(None));
+        for name in [MUSIC_BUS, SFX_BUS, VOICE_BUS] {
+            buses.insert(name.to_owned(), Bus::new(Some(MASTER_BUS.to_owned())));
+        }
+        let mut buses = Self {
+            buses,
+            states: HashMap::new(),
+        };
+        buses.refresh_states();
+        buses
+    }
+
+    fn bus(&self, name: &str) -> GameResult<&Bus> {
+        self.buses.get(name)
+            .ok_or_else(|| GameError::RuntimeError(format!("no bus named `{}`", name).into()))
+    }
+
+    fn bus_mut(&mut self, name: &str) -> GameResult<&mut Bus> {
+        self.buses.get_mut(name)
+            .ok_or_else(|| GameError::RuntimeError(format!("no bus named `{}`", name).into()))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.buses.contains_key(name)
+    }
+
+    pub fn validate(&self, name: &str) -> GameResult {
+        self.bus(name).map(|_| ())
+    }
+
+    pub fn add(&mut self, name: &str, parent: &str) -> GameResult {
+        if self.contains(name) {
+            return Err(GameError::RuntimeError(format!("bus `{}` already exists", name).into()));
+        }
+        self.validate(parent)?;
+        self.buses.insert(name.to_owned(), Bus::new(Some(parent.to_owned())));
+        self.refresh_states();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> GameResult<String> {
+        if [MASTER_BUS, MUSIC_BUS, SFX_BUS, VOICE_BUS].contains(&name) {
+            return Err(GameError::RuntimeError(format!("bus `{}` can not be removed", name).into()));
+        }
+        let parent = self.bus(name)?.parent.clone().unwrap_or_else(|| MASTER_BUS.to_owned());
+        self.buses.remove(name);
+        for bus in self.buses.values_mut() {
+            if bus.parent.as_deref() == Some(name) {
+                bus.parent = Some(parent.clone());
+            }
+        }
+        self.refresh_states();
+        Ok(parent)
+    }
+
+    pub fn parent(&self, name: &str) -> Option<&str> {
+        self.buses.get(name).and_then(|bus| bus.parent.as_deref())
+    }
+
+    pub fn volume(&self, name: &str) -> Option<f32> {
+        self.buses.get(name).map(|bus| bus.volume)
+    }
+
+    pub fn set_volume(&mut self, name: &str, volume: f32) -> GameResult {
+        self.bus_mut(name)?.volume = volume.max(0.0);
+        self.refresh_states();
+        Ok(())
+    }
+
+    pub fn is_muted(&self, name: &str) -> Option<bool> {
+        self.buses.get(name).map(|bus| bus.muted)
+    }
+
+    pub fn set_muted(&mut self, name: &str, muted: bool) -> GameResult {
+        self.bus_mut(name)?.muted = muted;
+        self.refresh_states();
+        Ok(())
+    }
+
+    pub fn is_paused(&self, name: &str) -> Option<bool> {
+        self.buses.get(name).map(|bus| bus.paused)
+    }
+
+    pub fn set_paused(&mut self, name: &str, paused: bool) -> GameResult {
+        self.bus_mut(name)?.paused = paused;
+        self.refresh_states();
+        Ok(())
+    }
+
+    pub fn state(&self, name: &str) -> BusState {
+        self.states.get(name).copied().unwrap_or(BusState { gain: 1.0, paused: false })
+    }
+
+    fn refresh_states(&mut self) {
+        self.states.clear();
+        for name in self.buses.keys() {
+            let mut state = BusState { gain: 1.0, paused: false };
+            let mut current = Some(name.as_str());
+            while let Some(bus) = current.and_then(|name| self.buses.get(name)) {
+                if bus.muted {
+                    state.gain = 0.0;
+                }
+                state.gain *= bus.volume;
+                state.paused |= bus.paused;
+                current = bus.parent.as_deref();
+            }
+            self.states.insert(name.clone(), state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Buses, BusState, MASTER_BUS, MUSIC_BUS, SFX_BUS};
+
+    #[test]
+    fn test_nested_state() {
+        let mut buses = Buses::new();
+        buses.add("ambient", MUSIC_BUS).unwrap();
+        buses.set_volume(MASTER_BUS, 0.5).unwrap();
+        buses.set_volume(MUSIC_BUS, 0.5).unwrap();
+        buses.set_volume("ambient", 0.5).unwrap();
+        assert_eq!(buses.state("ambient"), BusState { gain: 0.125, paused: false });
+        buses.set_paused(MUSIC_BUS, true).unwrap();
+        assert_eq!(buses.state("ambient"), BusState { gain: 0.125, paused: true });
+        assert_eq!(buses.state(SFX_BUS), BusState { gain: 0.5, paused: false });
+        buses.set_muted(MASTER_BUS, true).unwrap();
+        assert_eq!(buses.state(SFX_BUS).gain, 0.0);
+        assert_eq!(buses.remove("ambient").unwrap(), MUSIC_BUS);
+        assert!(buses.remove(SFX_BUS).is_err());
+        assert!(buses.add("ambient", "unknown").is_err());
+    }
+}
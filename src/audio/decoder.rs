@@ -0,0 +1,118 @@
+use crate::error::{GameError, GameResult};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use std::io::{Cursor, ErrorKind};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub(crate) struct Decoder {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn symphonia::core::codecs::Decoder>,
+    track_id: u32,
+    sample_rate: u32,
+    channels: u16,
+    sample_buffer: Option<SampleBuffer<f32>>,
+    skip_frames: u64,
+}
+
+impl Decoder {
+    pub fn new(bytes: Arc<[u8]>) -> GameResult<Self> {
+        let media_source_stream = MediaSourceStream::new(Box::new(Cursor::new(bytes)), MediaSourceStreamOptions::default());
+        let probe_result = symphonia::default::get_probe()
+            .format(&Hint::new(), media_source_stream, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        let format = probe_result.format;
+        let track = format.tracks().iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or_else(|| GameError::InitError("no audio track".into()))?;
+        let track_id = track.id;
+        let sample_rate = track.codec_params.sample_rate
+            .ok_or_else(|| GameError::InitError("unknown audio sample rate".into()))?;
+        let channels = track.codec_params.channels
+            .map(|channels| channels.count() as u16)
+            .ok_or_else(|| GameError::InitError("unknown audio channels".into()))?;
+        if channels == 0 {
+            return Err(GameError::InitError("audio has no channel".into()));
+        }
+        let decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            sample_rate,
+            channels,
+            sample_buffer: None,
+            skip_frames: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn decode_next(&mut self, samples: &mut Vec<f32>) -> GameResult<bool> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(Error::IoError(error)) if error.kind() == ErrorKind::UnexpectedEof => return Ok(false),
+                Err(Error::ResetRequired) => return Ok(false),
+                Err(error) => return Err(GameError::RuntimeError(error.into())),
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+            let decoded = match self.decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                Err(Error::DecodeError(_)) => continue,
+                Err(error) => return Err(GameError::RuntimeError(error.into())),
+            };
+            if decoded.frames() == 0 {
+                continue;
+            }
+            let spec = *decoded.spec();
+            if spec.channels.count() as u16 != self.channels {
+                return Err(GameError::RuntimeError("audio channels changed while decoding".into()));
+            }
+            let sample_buffer = match &mut self.sample_buffer {
+                Some(sample_buffer) if sample_buffer.capacity() >= decoded.capacity() * spec.channels.count() => sample_buffer,
+                sample_buffer => sample_buffer.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+            };
+            sample_buffer.copy_interleaved_ref(decoded);
+            let channels = self.channels as usize;
+            let frame_count = sample_buffer.samples().len() / channels;
+            let skip_frames = (self.skip_frames as usize).min(frame_count);
+            self.skip_frames -= skip_frames as u64;
+            if skip_frames < frame_count {
+                samples.extend_from_slice(&sample_buffer.samples()[skip_frames * channels..]);
+                return Ok(true);
+            }
+        }
+    }
+
+    pub fn decode_all(&mut self) -> GameResult<Vec<f32>> {
+        let mut samples = Vec::new();
+        while self.decode_next(&mut samples)? {}
+        Ok(samples)
+    }
+
+    pub fn seek(&mut self, position: Duration) -> GameResult {
+        let seeked_to = self.format.seek(SeekMode::Accurate, SeekTo::Time {
+            time: position.into(),
+            track_id: Some(self.track_id),
+        }).map_err(|error| GameError::RuntimeError(error.into()))?;
+        self.decoder.reset();
+        self.skip_frames = seeked_to.required_ts.saturating_sub(seeked_to.actual_ts);
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Mixer, lock_mixer};
+use super::mixer::Voice;
+use super::Attenuation;
+use crate::error::GameResult;
+use crate::math::Position;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum SoundState {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+#[derive(Clone)]
+pub struct SoundInstance {
+    mixer: Arc<Mutex<Mixer>>,
+    id: u64,
+}
+
+impl SoundInstance {
+    pub(crate) fn new(mixer: Arc<Mutex<Mixer>>, id: u64) -> Self {
+        Self { mixer, id }
+    }
+
+    fn with_voice<R>(&self, f: impl FnOnce(&mut Voice) -> R) -> Option<R> {
+        lock_mixer(&self.mixer).voice(self.id).map(f)
+    }
+
+    pub fn state(&self) -> SoundState {
+        self.with_voice(|voice| {
+            if voice.is_paused() {
+                SoundState::Paused
+            } else {
+                SoundState::Playing
+            }
+        }).unwrap_or(SoundState::Stopped)
+    }
+
+    pub fn pause(&self) {
+        self.with_voice(|voice| voice.set_paused(true));
+    }
+
+    pub fn resume(&self) {
+        self.with_voice(|voice| voice.set_paused(false));
+    }
+
+    pub fn stop(&self) {
+        lock_mixer(&self.mixer).stop(self.id);
+    }
+
+    pub fn bus(&self) -> Option<String> {
+        self.with_voice(|voice| voice.bus().to_owned())
+    }
+
+    pub fn set_bus(&self, bus: &str) -> GameResult {
+        lock_mixer(&self.mixer).route(self.id, bus)
+    }
+
+    pub fn position(&self) -> Duration {
+        self.with_voice(|voice| voice.position()).unwrap_or(Duration::ZERO)
+    }
+
+    pub fn seek(&self, position: Duration) {
+        self.with_voice(|voice| voice.seek(position));
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.with_voice(|voice| voice.is_looping()).unwrap_or(false)
+    }
+
+    pub fn set_looping(&self, looping: bool) {
+        self.with_voice(|voice| voice.set_looping(looping));
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.with_voice(|voice| voice.volume()).unwrap_or(0.0)
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        self.with_voice(|voice| voice.set_volume(volume));
+    }
+
+    pub fn pitch(&self) -> f32 {
+        self.with_voice(|voice| voice.pitch()).unwrap_or(1.0)
+    }
+
+    pub fn set_pitch(&self, pitch: f32) {
+        self.with_voice(|voice| voice.set_pitch(pitch));
+    }
+
+    pub fn pan(&self) -> f32 {
+        self.with_voice(|voice| voice.pan()).unwrap_or(0.0)
+    }
+
+    pub fn set_pan(&self, pan: f32) {
+        self.with_voice(|voice| voice.set_pan(pan));
+    }
+
+    pub fn world_position(&self) -> Option<Position> {
+        self.with_voice(|voice| voice.world_position()).flatten()
+    }
+
+    pub fn set_world_position(&self, world_position: Option<impl Into<Position>>) {
+        let world_position = world_position.map(|world_position| world_position.into());
+        self.with_voice(|voice| voice.set_world_position(world_position));
+    }
+
+    pub fn attenuation(&self) -> Option<Attenuation> {
+        self.with_voice(|voice| voice.attenuation()).flatten()
+    }
+
+    pub fn set_attenuation(&self, attenuation: Option<Attenuation>) {
+        self.with_voice(|voice| voice.set_attenuation(attenuation));
+    }
+
+    pub fn fade_in(&self, duration: Duration) {
+        self.with_voice(|voice| voice.fade(Some(0.0), 1.0, duration, false));
+    }
+
+    pub fn fade_out(&self, duration: Duration) {
+        self.with_voice(|voice| voice.fade(None, 0.0, duration, true));
+    }
+}
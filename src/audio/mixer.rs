@@ -0,0 +1,461 @@
+use super::bus::Buses;
+use super::spatial::{Attenuation, Listener};
+use crate::error::GameResult;
+use crate::math::Position;
+use std::time::Duration;
+
+pub(crate) type Frame = [f32; 2];
+
+pub(crate) const SILENCE: Frame = [0.0, 0.0];
+
+pub(crate) trait Source: Send {
+    fn sample_rate(&self) -> u32;
+
+    fn next_frame(&mut self) -> Option<Frame>;
+
+    fn seek(&mut self, position: Duration);
+}
+
+struct Fade {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    elapsed: Duration,
+    stop_on_finish: bool,
+}
+
+pub(crate) struct Voice {
+    source: Box<dyn Source>,
+    bus: String,
+    current: Option<Frame>,
+    next: Option<Frame>,
+    fraction: f64,
+    position: u64,
+    restarted: bool,
+    paused: bool,
+    looping: bool,
+    volume: f32,
+    pitch: f32,
+    pan: f32,
+    fade_gain: f32,
+    fade: Option<Fade>,
+    world_position: Option<Position>,
+    attenuation: Option<Attenuation>,
+}
+
+impl Voice {
+    fn new(source: Box<dyn Source>, bus: String) -> Self {
+        let mut voice = Self {
+            source,
+            bus,
+            current: None,
+            next: None,
+            fraction: 0.0,
+            position: 0,
+            restarted: false,
+            paused: false,
+            looping: false,
+            volume: 1.0,
+            pitch: 1.0,
+            pan: 0.0,
+            fade_gain: 1.0,
+            fade: None,
+            world_position: None,
+            attenuation: None,
+        };
+        voice.current = voice.pull();
+        voice.next = voice.pull();
+        voice
+    }
+
+    fn is_finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    fn pull(&mut self) -> Option<Frame> {
+        self.restarted = false;
+        match self.source.next_frame() {
+            Some(frame) => Some(frame),
+            None if self.looping => {
+                self.source.seek(Duration::ZERO);
+                self.restarted = true;
+                self.source.next_frame()
+            }
+            None => None,
+        }
+    }
+
+    fn next_frame(&mut self, step: f64) -> Option<Frame> {
+        let current = self.current?;
+        let next = self.next.unwrap_or(SILENCE);
+        let t = self.fraction as f32;
+        let frame = [
+            current[0] + (next[0] - current[0]) * t,
+            current[1] + (next[1] - current[1]) * t,
+        ];
+        self.fraction += step;
+        while self.fraction >= 1.0 && self.current.is_some() {
+            self.fraction -= 1.0;
+            let restarted = self.restarted;
+            self.current = self.next;
+            self.next = self.pull();
+            self.position = if restarted { 0 } else { self.position + 1 };
+        }
+        Some(frame)
+    }
+
+    fn gains(&self, listener: &Listener) -> Frame {
+        let mut gain = self.volume * self.fade_gain;
+        let mut pan = self.pan;
+        if let Some(world_position) = self.world_position {
+            let (spatial_gain, spatial_pan) = listener.spatialize(world_position, self.attenuation);
+            gain *= spatial_gain;
+            pan = (pan + spatial_pan).clamp(-1.0, 1.0);
+        }
+        [
+            gain * (1.0 - pan).min(1.0),
+            gain * (1.0 + pan).min(1.0),
+        ]
+    }
+
+    fn update(&mut self, delta_time: Duration) {
+        if self.paused {
+            return;
+        }
+        if let Some(fade) = &mut self.fade {
+            fade.elapsed = (fade.elapsed + delta_time).min(fade.duration);
+            let progress = if fade.duration.is_zero() {
+                1.0
+            } else {
+                fade.elapsed.as_secs_f32() / fade.duration.as_secs_f32()
+            };
+            self.fade_gain = fade.from + (fade.to - fade.from) * progress;
+            if fade.elapsed >= fade.duration {
+                if fade.stop_on_finish {
+                    self.current = None;
+                }
+                self.fade = None;
+            }
+        }
+    }
+
+    pub fn bus(&self) -> &str {
+        &self.bus
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn position(&self) -> Duration {
+        Duration::from_secs_f64(self.position as f64 / self.source.sample_rate() as f64)
+    }
+
+    pub fn seek(&mut self, position: Duration) {
+        self.source.seek(position);
+        self.position = (position.as_secs_f64() * self.source.sample_rate() as f64) as u64;
+        self.fraction = 0.0;
+        self.current = self.pull();
+        self.next = self.pull();
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+        if looping && self.current.is_some() && self.next.is_none() {
+            self.next = self.pull();
+        }
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.max(0.0);
+    }
+
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch.max(0.0);
+    }
+
+    pub fn pan(&self) -> f32 {
+        self.pan
+    }
+
+    pub fn set_pan(&mut self, pan: f32) {
+        self.pan = pan.clamp(-1.0, 1.0);
+    }
+
+    pub fn world_position(&self) -> Option<Position> {
+        self.world_position
+    }
+
+    pub fn set_world_position(&mut self, world_position: Option<Position>) {
+        self.world_position = world_position;
+    }
+
+    pub fn attenuation(&self) -> Option<Attenuation> {
+        self.attenuation
+    }
+
+    pub fn set_attenuation(&mut self, attenuation: Option<Attenuation>) {
+        self.attenuation = attenuation;
+    }
+
+    pub fn fade(&mut self, from: Option<f32>, to: f32, duration: Duration, stop_on_finish: bool) {
+        let from = from.unwrap_or(self.fade_gain);
+        self.fade_gain = from;
+        self.fade = Some(Fade {
+            from,
+            to,
+            duration,
+            elapsed: Duration::ZERO,
+            stop_on_finish,
+        });
+    }
+}
+
+pub(crate) struct Mixer {
+    sample_rate: u32,
+    channels: u16,
+    voices: Vec<(u64, Voice)>,
+    next_voice_id: u64,
+    buses: Buses,
+    listener: Listener,
+}
+
+impl Mixer {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            voices: Vec::new(),
+            next_voice_id: 0,
+            buses: Buses::new(),
+            listener: Listener::new(),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Matches the output stream, sources are resampled to the mixer rate.
+    pub fn set_format(&mut self, sample_rate: u32, channels: u16) {
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+    }
+
+    pub fn buses(&self) -> &Buses {
+        &self.buses
+    }
+
+    pub fn buses_mut(&mut self) -> &mut Buses {
+        &mut self.buses
+    }
+
+    pub fn listener(&self) -> &Listener {
+        &self.listener
+    }
+
+    pub fn listener_mut(&mut self) -> &mut Listener {
+        &mut self.listener
+    }
+
+    pub fn play(&mut self, source: Box<dyn Source>, bus: &str) -> GameResult<u64> {
+        self.buses.validate(bus)?;
+        let id = self.next_voice_id;
+        self.next_voice_id += 1;
+        self.voices.push((id, Voice::new(source, bus.to_owned())));
+        Ok(id)
+    }
+
+    pub fn route(&mut self, id: u64, bus: &str) -> GameResult {
+        self.buses.validate(bus)?;
+        if let Some(voice) = self.voice(id) {
+            voice.bus = bus.to_owned();
+        }
+        Ok(())
+    }
+
+    pub fn remove_bus(&mut self, name: &str) -> GameResult {
+        let parent = self.buses.remove(name)?;
+        for (_, voice) in &mut self.voices {
+            if voice.bus == name {
+                voice.bus = parent.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn voice(&mut self, id: u64) -> Option<&mut Voice> {
+        self.voices.iter_mut()
+            .find(|(voice_id, _)| *voice_id == id)
+            .map(|(_, voice)| voice)
+    }
+
+    pub fn stop(&mut self, id: u64) {
+        self.voices.retain(|(voice_id, _)| *voice_id != id);
+    }
+
+    pub fn stop_all(&mut self) {
+        self.voices.clear();
+    }
+
+    pub fn update(&mut self, delta_time: Duration) {
+        for (_, voice) in &mut self.voices {
+            if !self.buses.state(&voice.bus).paused {
+                voice.update(delta_time);
+            }
+        }
+        self.voices.retain(|(_, voice)| !voice.is_finished());
+    }
+
+    pub fn mix(&mut self, output: &mut [f32]) {
+        output.fill(0.0);
+        let channels = self.channels as usize;
+        for (_, voice) in &mut self.voices {
+            let bus_state = self.buses.state(&voice.bus);
+            if voice.paused || bus_state.paused {
+                continue;
+            }
+            let step = voice.source.sample_rate() as f64 / self.sample_rate as f64 * voice.pitch as f64;
+            let gains = voice.gains(&self.listener);
+            let gains = [gains[0] * bus_state.gain, gains[1] * bus_state.gain];
+            for output_frame in output.chunks_mut(channels) {
+                match voice.next_frame(step) {
+                    Some(frame) => write_frame(output_frame, [frame[0] * gains[0], frame[1] * gains[1]]),
+                    None => break,
+                }
+            }
+        }
+        self.voices.retain(|(_, voice)| !voice.is_finished());
+        for sample in output {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+fn write_frame(output_frame: &mut [f32], frame: Frame) {
+    if output_frame.len() == 1 {
+        output_frame[0] += (frame[0] + frame[1]) / 2.0;
+    } else {
+        output_frame[0] += frame[0];
+        output_frame[1] += frame[1];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, Mixer, Source};
+    use crate::audio::bus::MASTER_BUS;
+    use std::time::Duration;
+
+    struct RampSource {
+        length: u64,
+        position: u64,
+    }
+
+    impl Source for RampSource {
+        fn sample_rate(&self) -> u32 {
+            10
+        }
+
+        fn next_frame(&mut self) -> Option<Frame> {
+            if self.position < self.length {
+                let value = self.position as f32 * 0.1;
+                self.position += 1;
+                Some([value, value])
+            } else {
+                None
+            }
+        }
+
+        fn seek(&mut self, position: Duration) {
+            self.position = (position.as_secs_f64() * 10.0) as u64;
+        }
+    }
+
+    fn play_ramp(mixer: &mut Mixer, length: u64) -> u64 {
+        mixer.play(Box::new(RampSource { length, position: 0 }), MASTER_BUS).unwrap()
+    }
+
+    fn mix_left(mixer: &mut Mixer, frames: usize) -> Vec<f32> {
+        let mut output = vec![0.0; frames * 2];
+        mixer.mix(&mut output);
+        output.chunks(2).map(|frame| (frame[0] * 10.0).round() / 10.0).collect()
+    }
+
+    #[test]
+    fn test_pause_resume() {
+        let mut mixer = Mixer::new(10, 2);
+        let id = play_ramp(&mut mixer, 10);
+        mixer.voice(id).unwrap().set_paused(true);
+        assert_eq!(mix_left(&mut mixer, 2), vec![0.0, 0.0]);
+        assert_eq!(mixer.voice(id).unwrap().position(), Duration::ZERO);
+        mixer.voice(id).unwrap().set_paused(false);
+        assert_eq!(mix_left(&mut mixer, 2), vec![0.0, 0.1]);
+        assert_eq!(mixer.voice(id).unwrap().position(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut mixer = Mixer::new(10, 2);
+        let id = play_ramp(&mut mixer, 10);
+        mixer.voice(id).unwrap().seek(Duration::from_millis(500));
+        assert_eq!(mixer.voice(id).unwrap().position(), Duration::from_millis(500));
+        assert_eq!(mix_left(&mut mixer, 2), vec![0.5, 0.6]);
+    }
+
+    #[test]
+    fn test_loop() {
+        let mut mixer = Mixer::new(10, 2);
+        let id = play_ramp(&mut mixer, 4);
+        mixer.voice(id).unwrap().set_looping(true);
+        assert_eq!(mix_left(&mut mixer, 6), vec![0.0, 0.1, 0.2, 0.3, 0.0, 0.1]);
+        assert_eq!(mixer.voice(id).unwrap().position(), Duration::from_millis(200));
+        mixer.voice(id).unwrap().set_looping(false);
+        assert_eq!(mix_left(&mut mixer, 3), vec![0.2, 0.3, 0.0]);
+        assert!(mixer.voice(id).is_none());
+    }
+
+    #[test]
+    fn test_pitch() {
+        let mut mixer = Mixer::new(10, 2);
+        let id = play_ramp(&mut mixer, 10);
+        mixer.voice(id).unwrap().set_pitch(2.0);
+        assert_eq!(mix_left(&mut mixer, 2), vec![0.0, 0.2]);
+        assert_eq!(mixer.voice(id).unwrap().position(), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_fade() {
+        let mut mixer = Mixer::new(10, 2);
+        let id = play_ramp(&mut mixer, 10);
+        mixer.voice(id).unwrap().seek(Duration::from_millis(400));
+        mixer.voice(id).unwrap().fade(Some(0.0), 1.0, Duration::from_secs(1), false);
+        mixer.update(Duration::from_millis(500));
+        assert_eq!(mix_left(&mut mixer, 1), vec![0.2]);
+        mixer.update(Duration::from_secs(1));
+        assert_eq!(mix_left(&mut mixer, 1), vec![0.5]);
+        mixer.voice(id).unwrap().fade(None, 0.0, Duration::from_secs(1), true);
+        mixer.update(Duration::from_secs(1));
+        assert!(mixer.voice(id).is_none());
+    }
+}
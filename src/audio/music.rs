@@ -0,0 +1,248 @@
+use super::decoder::Decoder;
+use super::mixer::{Source, Frame, SILENCE};
+use super::sound::to_frame;
+use crate::error::GameResult;
+use crate::engine::Engine;
+use crate::filesystem::Reload;
+use std::sync::{Arc, Weak, RwLock};
+use std::sync::mpsc::{self, Sender, Receiver, SyncSender, TryRecvError};
+use std::thread;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const STREAM_BUFFER_PACKETS: usize = 16;
+
+struct MusicData {
+    bytes: Arc<[u8]>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl MusicData {
+    fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        let bytes: Arc<[u8]> = bytes.into();
+        let decoder = Decoder::new(bytes.clone())?;
+        Ok(Self {
+            bytes,
+            sample_rate: decoder.sample_rate(),
+            channels: decoder.channels(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Music {
+    data: Arc<RwLock<Arc<MusicData>>>,
+}
+
+impl Music {
+    pub fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        let data = MusicData::from_bytes(bytes)?;
+        Ok(Self {
+            data: Arc::new(RwLock::new(Arc::new(data))),
+        })
+    }
+
+    pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let bytes = engine.filesystem().read(path)?;
+        let music = Self::from_bytes(&bytes)?;
+        music.watch(engine, path);
+        Ok(music)
+    }
+
+    pub(crate) fn watch(&self, engine: &mut Engine, path: &Path) {
+        engine.watch_asset(vec![path.to_owned()], MusicReload {
+            data: Arc::downgrade(&self.data),
+            path: path.to_owned(),
+        });
+    }
+
+    fn data(&self) -> Arc<MusicData> {
+        self.data.read().unwrap_or_else(|error| error.into_inner()).clone()
+    }
+
+    /// Decoding runs ahead on its own thread, so the mixer only takes already decoded samples.
+    pub(crate) fn source(&self) -> Box<dyn Source> {
+        let data = self.data();
+        let (packet_sender, packets) = mpsc::sync_channel(STREAM_BUFFER_PACKETS);
+        let (seeks, seek_receiver) = mpsc::channel();
+        let bytes = data.bytes.clone();
+        thread::spawn(move || decode_stream(bytes, packet_sender, seek_receiver));
+        let mut source = MusicSource {
+            sample_rate: data.sample_rate,
+            channels: data.channels as usize,
+            packets,
+            seeks,
+            tag: (0, 0),
+            ended: false,
+            samples: Vec::new(),
+            position: 0,
+        };
+        // Waits for the first packet, so playback does not start with an underrun.
+        if let Ok(packet) = source.packets.recv() {
+            source.receive(packet);
+        }
+        Box::new(source)
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.data().sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.data().channels
+    }
+}
+
+struct MusicReload {
+    data: Weak<RwLock<Arc<MusicData>>>,
+    path: PathBuf,
+}
+
+impl Reload for MusicReload {
+    fn is_alive(&self) -> bool {
+        self.data.strong_count() > 0
+    }
+
+    fn reload(&self, engine: &mut Engine) -> GameResult {
+        let bytes = engine.filesystem().read(&self.path)?;
+        let data = MusicData::from_bytes(&bytes)?;
+        if let Some(old_data) = self.data.upgrade() {
+            *old_data.write().unwrap_or_else(|error| error.into_inner()) = Arc::new(data);
+        }
+        Ok(())
+    }
+}
+
+/// The seek a packet follows and how many times the stream restarted from the beginning since.
+type StreamTag = (u64, u64);
+
+enum StreamPacket {
+    Samples(StreamTag, Vec<f32>),
+    End(StreamTag),
+}
+
+fn decode_stream(bytes: Arc<[u8]>, packets: SyncSender<StreamPacket>, seeks: Receiver<(StreamTag, Duration)>) {
+    let mut tag = (0, 0);
+    let mut decoder = Decoder::new(bytes.clone()).ok();
+    loop {
+        if let Some((seek_tag, position)) = seeks.try_iter().last() {
+            tag = seek_tag;
+            decoder = Decoder::new(bytes.clone()).ok();
+            if let Some(seeking_decoder) = &mut decoder
+                && !position.is_zero()
+                && seeking_decoder.seek(position).is_err() {
+                decoder = None;
+            }
+        }
+        let mut samples = Vec::new();
+        let packet = match decoder.as_mut().map(|decoder| decoder.decode_next(&mut samples)) {
+            Some(Ok(true)) => StreamPacket::Samples(tag, samples),
+            _ => {
+                // Goes on from the beginning, so a looping voice finds the start already decoded.
+                decoder = Decoder::new(bytes.clone()).ok();
+                let packet = StreamPacket::End(tag);
+                tag.1 += 1;
+                packet
+            }
+        };
+        // Blocks while the buffer is full and fails once the source is dropped.
+        if packets.send(packet).is_err() {
+            return;
+        }
+    }
+}
+
+struct MusicSource {
+    sample_rate: u32,
+    channels: usize,
+    packets: Receiver<StreamPacket>,
+    seeks: Sender<(StreamTag, Duration)>,
+    tag: StreamTag,
+    ended: bool,
+    samples: Vec<f32>,
+    position: usize,
+}
+
+impl MusicSource {
+    fn receive(&mut self, packet: StreamPacket) {
+        match packet {
+            StreamPacket::Samples(tag, samples) if tag == self.tag => {
+                self.samples = samples;
+                self.position = 0;
+            }
+            StreamPacket::End(tag) if tag == self.tag => self.ended = true,
+            _ => (),
+        }
+    }
+}
+
+impl Source for MusicSource {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        while (self.position + 1) * self.channels > self.samples.len() {
+            if self.ended {
+                return None;
+            }
+            match self.packets.try_recv() {
+                Ok(packet) => self.receive(packet),
+                // The decoder is behind, silent until it catches up.
+                Err(TryRecvError::Empty) => return Some(SILENCE),
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+        let index = self.position * self.channels;
+        self.position += 1;
+        Some(to_frame(&self.samples[index..index + self.channels]))
+    }
+
+    fn seek(&mut self, position: Duration) {
+        self.samples.clear();
+        self.position = 0;
+        if position.is_zero() && self.ended {
+            self.tag.1 += 1;
+        } else {
+            self.tag = (self.tag.0 + 1, 0);
+            let _ = self.seeks.send((self.tag, position));
+        }
+        self.ended = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Music;
+    use crate::audio::mixer::{Frame, Source, SILENCE};
+    use crate::audio::sound::tests::wav_bytes;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Skips the silence of underruns, the test file itself is never silent.
+    fn next_frames(source: &mut dyn Source, count: usize) -> Vec<Option<Frame>> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut frames = Vec::new();
+        while frames.len() < count && Instant::now() < deadline {
+            match source.next_frame() {
+                Some(SILENCE) => thread::yield_now(),
+                frame => frames.push(frame),
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn test_stream() {
+        let music = Music::from_bytes(&wav_bytes(4, 1, &[4096, 8192, 16384, -16384])).unwrap();
+        let frames = [Some([0.125, 0.125]), Some([0.25, 0.25]), Some([0.5, 0.5]), Some([-0.5, -0.5])];
+        let mut source = music.source();
+        assert_eq!(next_frames(source.as_mut(), 5), [&frames[..], &[None]].concat());
+        source.seek(Duration::ZERO);
+        assert_eq!(next_frames(source.as_mut(), 2), &frames[..2]);
+        source.seek(Duration::from_millis(500));
+        assert_eq!(next_frames(source.as_mut(), 3), [&frames[2..], &[None]].concat());
+    }
+}
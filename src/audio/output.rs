@@ -0,0 +1,180 @@
+use super::{AudioConfig, Mixer, lock_mixer};
+use crate::error::{GameError, GameResult};
+use cpal::{Stream, StreamConfig, SupportedStreamConfig, SampleRate, BufferSize, SampleFormat, SizedSample, FromSample};
+use cpal::traits::{HostTrait, DeviceTrait, StreamTrait};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const NULL_OUTPUT_BUFFER_SIZE: u32 = 1024;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum AudioBackend {
+    /// Opens the device with the configured sample rate and channels when supported, or with its default format otherwise.
+    /// Falls back to `Null`, as reported by `Audio::backend`, when no output device is available or its stream cannot be started.
+    Device,
+    Null,
+    /// Renders only on demand through `Audio::render`, for deterministic tests. Time, fades included,
+    /// only advances with the rendered frames.
+    Offline,
+}
+
+pub(crate) enum Output {
+    Device(Stream),
+    Null(NullOutput),
+    Offline,
+}
+
+impl Output {
+    pub fn new(audio_config: &AudioConfig, mixer: Arc<Mutex<Mixer>>) -> GameResult<Self> {
+        match audio_config.backend {
+            AudioBackend::Device => {
+                let host = cpal::default_host();
+                let stream = host.default_output_device()
+                    .and_then(|device| build_device_stream(&device, audio_config, mixer.clone()).ok());
+                match stream {
+                    Some(stream) => Ok(Self::Device(stream)),
+                    None => {
+                        lock_mixer(&mixer).set_format(audio_config.sample_rate, audio_config.channels);
+                        Ok(Self::Null(NullOutput::new(audio_config, mixer)))
+                    }
+                }
+            }
+            AudioBackend::Null => Ok(Self::Null(NullOutput::new(audio_config, mixer))),
+            AudioBackend::Offline => Ok(Self::Offline),
+        }
+    }
+
+    pub fn backend(&self) -> AudioBackend {
+        match self {
+            Self::Device(_) => AudioBackend::Device,
+            Self::Null(_) => AudioBackend::Null,
+            Self::Offline => AudioBackend::Offline,
+        }
+    }
+
+    pub fn play(&self) {
+        match self {
+            Self::Device(stream) => {
+                let _ = stream.play();
+            }
+            Self::Null(null_output) => null_output.paused.store(false, Ordering::Relaxed),
+            Self::Offline => (),
+        }
+    }
+
+    pub fn pause(&self) {
+        match self {
+            Self::Device(stream) => {
+                let _ = stream.pause();
+            }
+            Self::Null(null_output) => null_output.paused.store(true, Ordering::Relaxed),
+            Self::Offline => (),
+        }
+    }
+}
+
+fn select_device_config(device: &cpal::Device, audio_config: &AudioConfig) -> GameResult<SupportedStreamConfig> {
+    let sample_rate = SampleRate(audio_config.sample_rate);
+    let supported_config = device.supported_output_configs().ok()
+        .and_then(|mut supported_configs| supported_configs.find(|supported_config| {
+            supported_config.channels() == audio_config.channels
+                && supported_config.min_sample_rate() <= sample_rate
+                && sample_rate <= supported_config.max_sample_rate()
+        }));
+    match supported_config {
+        Some(supported_config) => Ok(supported_config.with_sample_rate(sample_rate)),
+        None => device.default_output_config()
+            .map_err(|error| GameError::InitError(error.into())),
+    }
+}
+
+fn build_device_stream(device: &cpal::Device, audio_config: &AudioConfig, mixer: Arc<Mutex<Mixer>>) -> GameResult<Stream> {
+    let device_config = select_device_config(device, audio_config)?;
+    let sample_format = device_config.sample_format();
+    let stream_config = StreamConfig {
+        channels: device_config.channels(),
+        sample_rate: device_config.sample_rate(),
+        buffer_size: match audio_config.buffer_size {
+            Some(buffer_size) => BufferSize::Fixed(buffer_size),
+            None => BufferSize::Default,
+        },
+    };
+    lock_mixer(&mixer).set_format(stream_config.sample_rate.0, stream_config.channels);
+    let stream = match sample_format {
+        SampleFormat::I8 => build_typed_stream::<i8>(device, &stream_config, mixer),
+        SampleFormat::I16 => build_typed_stream::<i16>(device, &stream_config, mixer),
+        SampleFormat::I32 => build_typed_stream::<i32>(device, &stream_config, mixer),
+        SampleFormat::I64 => build_typed_stream::<i64>(device, &stream_config, mixer),
+        SampleFormat::U8 => build_typed_stream::<u8>(device, &stream_config, mixer),
+        SampleFormat::U16 => build_typed_stream::<u16>(device, &stream_config, mixer),
+        SampleFormat::U32 => build_typed_stream::<u32>(device, &stream_config, mixer),
+        SampleFormat::U64 => build_typed_stream::<u64>(device, &stream_config, mixer),
+        SampleFormat::F64 => build_typed_stream::<f64>(device, &stream_config, mixer),
+        _ => build_typed_stream::<f32>(device, &stream_config, mixer),
+    }?;
+    stream.play().map_err(|error| GameError::InitError(error.into()))?;
+    Ok(stream)
+}
+
+fn build_typed_stream<T>(device: &cpal::Device, stream_config: &StreamConfig, mixer: Arc<Mutex<Mixer>>) -> GameResult<Stream>
+    where
+        T: SizedSample + FromSample<f32>,
+{
+    let mut buffer = Vec::new();
+    device.build_output_stream(
+        stream_config,
+        move |data: &mut [T], _| {
+            buffer.resize(data.len(), 0.0);
+            lock_mixer(&mixer).mix(&mut buffer);
+            for (sample, value) in data.iter_mut().zip(&buffer) {
+                *sample = T::from_sample(*value);
+            }
+        },
+        |_| (),
+        None,
+    ).map_err(|error| GameError::InitError(error.into()))
+}
+
+pub(crate) struct NullOutput {
+    paused: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl NullOutput {
+    fn new(audio_config: &AudioConfig, mixer: Arc<Mutex<Mixer>>) -> Self {
+        let paused = Arc::new(AtomicBool::new(false));
+        let running = Arc::new(AtomicBool::new(true));
+        let buffer_size = audio_config.buffer_size.unwrap_or(NULL_OUTPUT_BUFFER_SIZE);
+        let period = Duration::from_secs_f64(buffer_size as f64 / audio_config.sample_rate as f64);
+        let mut buffer = vec![0.0; buffer_size as usize * audio_config.channels as usize];
+        let thread = {
+            let paused = paused.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    thread::sleep(period);
+                    if !paused.load(Ordering::Relaxed) {
+                        lock_mixer(&mixer).mix(&mut buffer);
+                    }
+                }
+            })
+        };
+        Self {
+            paused,
+            running,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for NullOutput {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use super::decoder::Decoder;
+use super::mixer::{Source, Frame};
+use crate::error::{GameError, GameResult};
+use crate::engine::Engine;
+use crate::filesystem::Reload;
+use std::sync::{Arc, Weak, RwLock};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+struct SoundData {
+    sample_rate: u32,
+    channels: u16,
+    samples: Arc<[f32]>,
+}
+
+impl SoundData {
+    fn new(sample_rate: u32, channels: u16, samples: Vec<f32>) -> GameResult<Self> {
+        validate_samples(sample_rate, channels, &samples)?;
+        Ok(Self {
+            sample_rate,
+            channels,
+            samples: samples.into(),
+        })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        let mut decoder = Decoder::new(bytes.into())?;
+        let samples = decoder.decode_all()
+            .map_err(|error| GameError::InitError(error.into()))?;
+        Self::new(decoder.sample_rate(), decoder.channels(), samples)
+    }
+
+    fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels as usize
+    }
+}
+
+#[derive(Clone)]
+pub struct Sound {
+    data: Arc<RwLock<Arc<SoundData>>>,
+}
+
+impl Sound {
+    fn from_data(data: SoundData) -> Self {
+        Self {
+            data: Arc::new(RwLock::new(Arc::new(data))),
+        }
+    }
+
+    fn data(&self) -> Arc<SoundData> {
+        self.data.read().unwrap_or_else(|error| error.into_inner()).clone()
+    }
+
+    pub fn new(sample_rate: u32, channels: u16, samples: Vec<f32>) -> GameResult<Self> {
+        SoundData::new(sample_rate, channels, samples).map(Self::from_data)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        SoundData::from_bytes(bytes).map(Self::from_data)
+    }
+
+    pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let bytes = engine.filesystem().read(path)?;
+        let sound = Self::from_bytes(&bytes)?;
+        sound.watch(engine, path);
+        Ok(sound)
+    }
+
+    pub(crate) fn watch(&self, engine: &mut Engine, path: &Path) {
+        engine.watch_asset(vec![path.to_owned()], SoundReload {
+            data: Arc::downgrade(&self.data),
+            path: path.to_owned(),
+        });
+    }
+
+    pub(crate) fn source(&self) -> Box<dyn Source> {
+        Box::new(SoundSource {
+            data: self.data(),
+            position: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.data().sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.data().channels
+    }
+
+    pub fn samples(&self) -> Arc<[f32]> {
+        self.data().samples.clone()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.data().frame_count()
+    }
+
+    pub fn duration(&self) -> Duration {
+        let data = self.data();
+        Duration::from_secs_f64(data.frame_count() as f64 / data.sample_rate as f64)
+    }
+}
+
+struct SoundReload {
+    data: Weak<RwLock<Arc<SoundData>>>,
+    path: PathBuf,
+}
+
+impl Reload for SoundReload {
+    fn is_alive(&self) -> bool {
+        self.data.strong_count() > 0
+    }
+
+    fn reload(&self, engine: &mut Engine) -> GameResult {
+        let bytes = engine.filesystem().read(&self.path)?;
+        let data = SoundData::from_bytes(&bytes)?;
+        if let Some(old_data) = self.data.upgrade() {
+            *old_data.write().unwrap_or_else(|error| error.into_inner()) = Arc::new(data);
+        }
+        Ok(())
+    }
+}
+
+fn validate_samples(sample_rate: u32, channels: u16, samples: &[f32]) -> GameResult {
+    if sample_rate == 0 {
+        return Err(GameError::RuntimeError("illegal sample rate".into()));
+    }
+    if channels == 0 {
+        return Err(GameError::RuntimeError("illegal channels".into()));
+    }
+    if !samples.len().is_multiple_of(channels as usize) {
+        return Err(GameError::RuntimeError("illegal samples length".into()));
+    }
+    Ok(())
+}
+
+struct SoundSource {
+    data: Arc<SoundData>,
+    position: usize,
+}
+
+impl Source for SoundSource {
+    fn sample_rate(&self) -> u32 {
+        self.data.sample_rate
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        let channels = self.data.channels as usize;
+        let index = self.position * channels;
+        let samples = self.data.samples.get(index..index + channels)?;
+        self.position += 1;
+        Some(to_frame(samples))
+    }
+
+    fn seek(&mut self, position: Duration) {
+        self.position = (position.as_secs_f64() * self.data.sample_rate as f64) as usize;
+    }
+}
+
+pub(crate) fn to_frame(samples: &[f32]) -> Frame {
+    if samples.len() == 1 {
+        [samples[0], samples[0]]
+    } else {
+        [samples[0], samples[1]]
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Sound, SoundData};
+    use crate::error::GameError;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    pub(crate) fn wav_bytes(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let data_size = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let bytes = wav_bytes(22050, 2, &[0, 16384, -16384, 0, 0, 0]);
+        let sound = Sound::from_bytes(&bytes).unwrap();
+        assert_eq!(sound.sample_rate(), 22050);
+        assert_eq!(sound.channels(), 2);
+        assert_eq!(sound.frame_count(), 3);
+        assert_eq!(&*sound.samples(), &[0.0, 0.5, -0.5, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_from_malformed_bytes() {
+        let result = Sound::from_bytes(b"not a sound");
+        assert!(matches!(result, Err(GameError::InitError(_))));
+    }
+
+    #[test]
+    fn test_reloaded_data() {
+        let sound = Sound::new(10, 1, vec![0.0; 10]).unwrap();
+        let samples = sound.samples();
+        *sound.data.write().unwrap() = Arc::new(SoundData::new(20, 2, vec![0.5; 80]).unwrap());
+        assert_eq!(sound.sample_rate(), 20);
+        assert_eq!(sound.channels(), 2);
+        assert_eq!(sound.frame_count(), 40);
+        assert_eq!(sound.duration(), Duration::from_secs(2));
+        assert_eq!(&*sound.samples(), &[0.5; 80]);
+        assert_eq!(samples.len(), 10);
+    }
+}
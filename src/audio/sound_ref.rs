@@ -0,0 +1,36 @@
+use super::{Sound, Music, MUSIC_BUS, SFX_BUS};
+use super::mixer::Source;
+
+#[derive(Copy, Clone)]
+pub enum SoundRef<'a> {
+    Sound(&'a Sound),
+    Music(&'a Music),
+}
+
+impl SoundRef<'_> {
+    pub(crate) fn source(&self) -> Box<dyn Source> {
+        match self {
+            Self::Sound(sound) => sound.source(),
+            Self::Music(music) => music.source(),
+        }
+    }
+
+    pub fn default_bus(&self) -> &'static str {
+        match self {
+            Self::Sound(_) => SFX_BUS,
+            Self::Music(_) => MUSIC_BUS,
+        }
+    }
+}
+
+impl<'a> From<&'a Sound> for SoundRef<'a> {
+    fn from(sound: &'a Sound) -> Self {
+        Self::Sound(sound)
+    }
+}
+
+impl<'a> From<&'a Music> for SoundRef<'a> {
+    fn from(music: &'a Music) -> Self {
+        Self::Music(music)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::math::{Position, Angle};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum DistanceModel {
+    Linear,
+    Inverse,
+    Exponential,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Attenuation {
+    pub model: DistanceModel,
+    pub reference_distance: f32,
+    pub max_distance: f32,
+    pub rolloff_factor: f32,
+}
+
+impl Attenuation {
+    pub fn new(model: DistanceModel, reference_distance: f32, max_distance: f32, rolloff_factor: f32) -> Self {
+        Self { model, reference_distance, max_distance, rolloff_factor }
+    }
+
+    pub(crate) fn gain(&self, distance: f32) -> f32 {
+        let reference_distance = self.reference_distance.max(f32::EPSILON);
+        let max_distance = self.max_distance.max(reference_distance);
+        let distance = distance.clamp(reference_distance, max_distance);
+        let gain = match self.model {
+            DistanceModel::Linear => {
+                if max_distance > reference_distance {
+                    1.0 - self.rolloff_factor * (distance - reference_distance) / (max_distance - reference_distance)
+                } else {
+                    1.0
+                }
+            }
+            DistanceModel::Inverse => reference_distance / (reference_distance + self.rolloff_factor * (distance - reference_distance)),
+            DistanceModel::Exponential => (distance / reference_distance).powf(-self.rolloff_factor),
+        };
+        gain.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Self::new(DistanceModel::Inverse, 100.0, 2000.0, 1.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Listener {
+    pub position: Position,
+    pub angle: Angle,
+    pub attenuation: Attenuation,
+}
+
+impl Listener {
+    pub fn new() -> Self {
+        Self {
+            position: Position::zero(),
+            angle: Angle::zero(),
+            attenuation: Attenuation::default(),
+        }
+    }
+
+    pub fn spatialize(&self, position: Position, attenuation: Option<Attenuation>) -> (f32, f32) {
+        let attenuation = attenuation.unwrap_or(self.attenuation);
+        let offset = position - self.position;
+        let (sin, cos) = self.angle.radians_value().sin_cos();
+        let local_x = offset.x * cos + offset.y * sin;
+        let distance = (offset.x * offset.x + offset.y * offset.y).sqrt();
+        let gain = attenuation.gain(distance);
+        let pan = (local_x / distance.max(attenuation.reference_distance).max(f32::EPSILON)).clamp(-1.0, 1.0);
+        (gain, pan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DistanceModel, Attenuation, Listener};
+    use crate::math::{Position, Angle};
+
+    #[test]
+    fn test_attenuation() {
+        let linear = Attenuation::new(DistanceModel::Linear, 10.0, 110.0, 1.0);
+        assert_eq!(linear.gain(0.0), 1.0);
+        assert_eq!(linear.gain(60.0), 0.5);
+        assert_eq!(linear.gain(200.0), 0.0);
+        let inverse = Attenuation::new(DistanceModel::Inverse, 10.0, 110.0, 1.0);
+        assert_eq!(inverse.gain(20.0), 0.5);
+        assert_eq!(inverse.gain(1000.0), inverse.gain(110.0));
+        let exponential = Attenuation::new(DistanceModel::Exponential, 10.0, 110.0, 2.0);
+        assert_eq!(exponential.gain(20.0), 0.25);
+    }
+
+    #[test]
+    fn test_spatialize() {
+        let mut listener = Listener::new();
+        listener.attenuation = Attenuation::new(DistanceModel::Linear, 10.0, 110.0, 1.0);
+        let (gain, pan) = listener.spatialize(Position::new(-60.0, 0.0), None);
+        assert_eq!(gain, 0.5);
+        assert_eq!(pan, -1.0);
+        let (_, pan) = listener.spatialize(Position::new(5.0, 0.0), None);
+        assert_eq!(pan, 0.5);
+        listener.angle = Angle::degrees(180.0);
+        let (_, pan) = listener.spatialize(Position::new(-60.0, 0.0), None);
+        assert!((pan - 1.0).abs() < 1e-6);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::error::{GameError, GameResult};
 use crate::math::Vector;
 use crate::event::{Event, KeyAction};
-use crate::filesystem::{Filesystem, FilesystemConfig};
+use crate::filesystem::{Filesystem, FilesystemConfig, Watcher, Reload};
 use crate::window::{Window, WindowConfig, LogicalPosition, LogicalSize};
 use crate::graphics::{Graphics, GraphicsConfig};
 use crate::timer::{Timer, TimerConfig};
@@ -11,10 +11,13 @@ use crate::touch::{Touch, TouchConfig};
 use crate::touchpad::{Touchpad, TouchpadConfig};
 use crate::gamepad::{Gamepad, GamepadConfig};
 use crate::audio::{Audio, AudioConfig};
+use crate::loader::{Loader, LoaderConfig};
+use crate::asset::Assets;
 use crate::game::Game;
 use winit::event_loop::{EventLoop, ControlFlow};
 use winit::event::{StartCause, WindowEvent, MouseScrollDelta};
 use winit::platform::run_return::EventLoopExtRunReturn;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 enum State {
@@ -27,6 +30,8 @@ enum State {
 pub struct Engine {
     event_loop: Option<EventLoop<()>>,
     filesystem: Filesystem,
+    // Dropped before `window`, so cached GL resources are released while the context is alive.
+    assets: Assets,
     window: Window,
     graphics: Graphics,
     timer: Timer,
@@ -36,6 +41,8 @@ pub struct Engine {
     touchpad: Touchpad,
     gamepad: Gamepad,
     audio: Audio,
+    loader: Loader,
+    watcher: Option<Watcher>,
     state: State,
 }
 
@@ -80,6 +87,14 @@ impl Engine {
         &mut self.audio
     }
 
+    pub fn loader(&mut self) -> &mut Loader {
+        &mut self.loader
+    }
+
+    pub fn assets(&mut self) -> &mut Assets {
+        &mut self.assets
+    }
+
     pub fn quit(&mut self) {
         match &self.state {
             State::Finished | State::Broken(_) => (),
@@ -94,6 +109,40 @@ impl Engine {
         }
     }
 
+    pub(crate) fn watch_asset(&mut self, paths: Vec<PathBuf>, reload: impl Reload + 'static) {
+        if let Some(watcher) = &mut self.watcher {
+            watcher.watch(&self.filesystem, paths, reload);
+        }
+    }
+
+    fn reload_assets(&mut self, game: &mut impl Game) -> GameResult {
+        let reloaded = match self.watcher.take() {
+            Some(mut watcher) => {
+                let reloaded = watcher.poll(self);
+                self.watcher = Some(watcher);
+                reloaded
+            }
+            None => return Ok(()),
+        };
+        if !reloaded.is_empty() {
+            self.graphics.rebind_program();
+        }
+        for (path, result) in reloaded {
+            let event = match result {
+                Ok(()) => Event::AssetReloaded(path),
+                Err(error) => Event::AssetReloadFailed(path, error.to_string()),
+            };
+            game.event(self, event)?;
+        }
+        Ok(())
+    }
+
+    fn finish_loads(&mut self) {
+        for finish in self.loader.take_finished() {
+            finish(self);
+        }
+    }
+
     fn handle_event(&mut self, event: winit::event::Event<()>, control_flow: &mut ControlFlow, game: &mut impl Game) -> GameResult {
         match event {
             winit::event::Event::NewEvents(start_cause) => {
@@ -252,6 +301,9 @@ impl Engine {
             winit::event::Event::RedrawRequested(window_id) => {
                 if window_id == self.window.window().id() {
                     if self.timer.tick_and_check() {
+                        self.reload_assets(game)?;
+                        self.finish_loads();
+                        self.audio.update(self.timer.delta_time());
                         game.update(self)?;
                         game.render(self)?;
                         self.graphics.present()?;
@@ -326,6 +378,7 @@ pub struct EngineBuilder {
     touchpad_config: Option<TouchpadConfig>,
     gamepad_config: Option<GamepadConfig>,
     audio_config: Option<AudioConfig>,
+    loader_config: Option<LoaderConfig>,
 }
 
 impl EngineBuilder {
@@ -341,6 +394,7 @@ impl EngineBuilder {
             touchpad_config: None,
             gamepad_config: None,
             audio_config: None,
+            loader_config: None,
         }
     }
 
@@ -394,6 +448,11 @@ impl EngineBuilder {
         self
     }
 
+    pub fn loader_config(mut self, loader_config: LoaderConfig) -> Self {
+        self.loader_config = Some(loader_config);
+        self
+    }
+
     pub fn build(self) -> GameResult<Engine> {
         let filesystem_config = self.filesystem_config.unwrap_or_else(|| FilesystemConfig::new());
         let window_config = self.window_config.unwrap_or_else(|| WindowConfig::new());
@@ -405,9 +464,11 @@ impl EngineBuilder {
         let touchpad_config = self.touchpad_config.unwrap_or_else(|| TouchpadConfig::new());
         let gamepad_config = self.gamepad_config.unwrap_or_else(|| GamepadConfig::new());
         let audio_config = self.audio_config.unwrap_or_else(|| AudioConfig::new());
+        let loader_config = self.loader_config.unwrap_or_default();
 
         let event_loop = EventLoop::new();
 
+        let watcher = filesystem_config.is_hot_reload_enabled().then(Watcher::new);
         let filesystem = Filesystem::new(filesystem_config)?;
         let window = Window::new(window_config, &event_loop, &filesystem)?;
         let graphics = Graphics::new(graphics_config, window.context_wrapper(), window.gl())?;
@@ -418,10 +479,13 @@ impl EngineBuilder {
         let touchpad = Touchpad::new(touchpad_config)?;
         let gamepad = Gamepad::new(gamepad_config)?;
         let audio = Audio::new(audio_config)?;
+        let loader = Loader::new(loader_config, filesystem.clone())?;
+        let assets = Assets::new();
 
         Ok(Engine {
             event_loop: Some(event_loop),
             filesystem,
+            assets,
             window,
             graphics,
             timer,
@@ -431,6 +495,8 @@ impl EngineBuilder {
             touchpad,
             gamepad,
             audio,
+            loader,
+            watcher,
             state: State::Ready,
         })
     }
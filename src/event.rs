@@ -4,6 +4,7 @@ use crate::keyboard::{KeyCode, ModifiersState};
 use crate::mouse::MouseButton;
 use crate::gamepad::{GamepadButton, GamepadAxis, GamepadId};
 use winit::event::ElementState;
+use std::path::PathBuf;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub(crate) enum KeyState {
@@ -109,4 +110,7 @@ pub enum Event {
         axis: GamepadAxis,
         value: f32,
     },
+    AssetReloaded(PathBuf),
+    /// The previous resource is kept, the next change of the file tries again.
+    AssetReloadFailed(PathBuf, String),
 }
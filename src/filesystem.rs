@@ -1,27 +1,398 @@
+mod root;
+mod archive;
+mod mount;
+mod watcher;
+
+use mount::MountedRoot;
+use root::not_found;
+
+pub use mount::Mount;
+pub(crate) use watcher::{Watcher, Reload};
+
 use crate::error::{GameError, GameResult};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::cmp::Reverse;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::io::Write;
+use std::fs::OpenOptions;
+use std::time::SystemTime;
 
-pub struct Filesystem {}
+const DEFAULT_USER_PREFIX: &str = "user";
+
+#[derive(Clone)]
+pub struct Filesystem {
+    mounts: Arc<Vec<MountedRoot>>,
+    user_prefix: String,
+    user_dir: Option<PathBuf>,
+}
 
 impl Filesystem {
-    pub(crate) fn new(_: FilesystemConfig) -> GameResult<Self> {
-        Ok(Self {})
+    pub(crate) fn new(filesystem_config: FilesystemConfig) -> GameResult<Self> {
+        let mut mounts = if filesystem_config.mounts.is_empty() {
+            vec![Mount::directory(".").open()?]
+        } else {
+            filesystem_config.mounts.iter()
+                .map(|mount| mount.open())
+                .collect::<GameResult<Vec<_>>>()?
+        };
+        mounts.reverse();
+        mounts.sort_by_key(|mount| Reverse(mount.priority()));
+        let user_prefix = filesystem_config.user_prefix;
+        let user_dir = match (filesystem_config.user_dir, &filesystem_config.app_id) {
+            (Some(user_dir), _) => Some(user_dir),
+            (None, Some(app_id)) => {
+                let app_id = normalize_path(app_id)
+                    .filter(|app_id| !app_id.is_empty())
+                    .ok_or_else(|| GameError::InitError(format!("illegal app id `{}`", app_id).into()))?;
+                let data_dir = dirs::data_dir()
+                    .ok_or_else(|| GameError::InitError("no user data directory".into()))?;
+                Some(data_dir.join(app_id))
+            }
+            (None, None) => None,
+        };
+        if let Some(user_dir) = &user_dir {
+            mounts.insert(0, Mount::directory(user_dir).prefix(&user_prefix).open()?);
+        }
+        Ok(Self {
+            mounts: Arc::new(mounts),
+            user_prefix,
+            user_dir,
+        })
+    }
+
+    pub fn user_dir(&self) -> Option<&Path> {
+        self.user_dir.as_deref()
+    }
+
+    pub fn user_prefix(&self) -> &str {
+        &self.user_prefix
+    }
+
+    fn user_path(&self, path: &Path) -> GameResult<PathBuf> {
+        let user_dir = self.user_dir.as_ref()
+            .ok_or_else(|| GameError::StateError("no user directory, app id is not set".into()))?;
+        let virtual_path = Self::virtual_path(path)?;
+        strip_path_prefix(&virtual_path, &self.user_prefix)
+            .map(|relative_path| user_dir.join(relative_path))
+            .ok_or_else(|| GameError::NotSupportedError(format!("path `{}` is not writable", virtual_path).into()))
+    }
+
+    fn create_parent_dir(path: &Path) -> GameResult {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| GameError::IoError(error.into()))?;
+        }
+        Ok(())
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> GameResult {
+        let path = self.user_path(path.as_ref())?;
+        Self::create_parent_dir(&path)?;
+        std::fs::write(path, contents).map_err(|error| GameError::IoError(error.into()))
+    }
+
+    pub fn append(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> GameResult {
+        let path = self.user_path(path.as_ref())?;
+        Self::create_parent_dir(&path)?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_ref()))
+            .map_err(|error| GameError::IoError(error.into()))
+    }
+
+    pub fn create_dir(&self, path: impl AsRef<Path>) -> GameResult {
+        let path = self.user_path(path.as_ref())?;
+        std::fs::create_dir_all(path).map_err(|error| GameError::IoError(error.into()))
+    }
+
+    pub fn remove(&self, path: impl AsRef<Path>) -> GameResult {
+        let path = self.user_path(path.as_ref())?;
+        if self.user_dir.as_deref() == Some(path.as_path()) {
+            return Err(GameError::NotSupportedError("user directory can not be removed".into()));
+        }
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        }.map_err(|error| GameError::IoError(error.into()))
+    }
+
+    /// Paths escaping the virtual root, such as `../assets/bunny.png`, are not mounted and are read
+    /// from the host filesystem relative to the working directory, like absolute paths.
+    fn virtual_path(path: &Path) -> GameResult<String> {
+        let path = path.to_str()
+            .ok_or_else(|| GameError::IoError(format!("illegal path `{}`", path.display()).into()))?;
+        normalize_path(path)
+            .ok_or_else(|| GameError::IoError(format!("illegal path `{}`", path).into()))
     }
 
     pub fn read(&self, path: impl AsRef<Path>) -> GameResult<Vec<u8>> {
-        std::fs::read(path).map_err(|error| GameError::IoError(error.into()))
+        let path = path.as_ref();
+        let virtual_path = match Self::virtual_path(path) {
+            Ok(virtual_path) => virtual_path,
+            Err(_) => return std::fs::read(path).map_err(|error| GameError::IoError(error.into())),
+        };
+        for mount in self.mounts.iter() {
+            if let Some(mount_path) = mount.resolve(&virtual_path) {
+                match mount.root().read(mount_path) {
+                    Ok(bytes) => return Ok(bytes),
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+                    Err(error) => return Err(GameError::IoError(error.into())),
+                }
+            }
+        }
+        if path.is_absolute() {
+            return std::fs::read(path).map_err(|error| GameError::IoError(error.into()));
+        }
+        Err(GameError::IoError(not_found(&virtual_path).into()))
     }
 
     pub fn read_to_string(&self, path: impl AsRef<Path>) -> GameResult<String> {
-        std::fs::read_to_string(path).map_err(|error| GameError::IoError(error.into()))
+        let bytes = self.read(path)?;
+        String::from_utf8(bytes).map_err(|error| GameError::IoError(error.into()))
+    }
+
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        match Self::virtual_path(path) {
+            Ok(virtual_path) => {
+                self.mounts.iter().any(|mount| {
+                    mount.resolve(&virtual_path).is_some_and(|mount_path| mount.root().exists(mount_path))
+                }) || (path.is_absolute() && path.exists())
+            }
+            Err(_) => path.exists(),
+        }
+    }
+
+    pub fn modified(&self, path: impl AsRef<Path>) -> Option<SystemTime> {
+        let path = path.as_ref();
+        if let Ok(virtual_path) = Self::virtual_path(path) {
+            for mount in self.mounts.iter() {
+                if let Some(mount_path) = mount.resolve(&virtual_path) && mount.root().exists(mount_path) {
+                    return mount.root().modified(mount_path);
+                }
+            }
+            if !path.is_absolute() {
+                return None;
+            }
+        }
+        std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    pub fn read_dir(&self, path: impl AsRef<Path>) -> GameResult<Vec<String>> {
+        let virtual_path = Self::virtual_path(path.as_ref())?;
+        let mut found = false;
+        let mut entries = BTreeSet::new();
+        for mount in self.mounts.iter() {
+            if let Some(mount_path) = mount.resolve(&virtual_path) {
+                match mount.root().read_dir(mount_path) {
+                    Ok(names) => {
+                        found = true;
+                        entries.extend(names);
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+                    Err(error) => return Err(GameError::IoError(error.into())),
+                }
+            } else if let Some(entry) = mount.child_of(&virtual_path) {
+                found = true;
+                entries.insert(entry.to_owned());
+            }
+        }
+        if found {
+            Ok(entries.into_iter().collect())
+        } else {
+            Err(GameError::IoError(not_found(&virtual_path).into()))
+        }
     }
 }
 
+#[macro_export]
+macro_rules! embed_files {
+    ($($path:literal),* $(,)?) => {
+        &[$(($path, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)) as &[u8])),*]
+    };
+}
+
+pub(crate) fn strip_path_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        Some(path)
+    } else if path == prefix {
+        Some("")
+    } else {
+        path.strip_prefix(prefix).and_then(|rest| rest.strip_prefix('/'))
+    }
+}
+
+pub(crate) fn normalize_path(path: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => (),
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
 #[derive(Debug, Clone)]
-pub struct FilesystemConfig {}
+pub struct FilesystemConfig {
+    mounts: Vec<Mount>,
+    app_id: Option<String>,
+    user_dir: Option<PathBuf>,
+    user_prefix: String,
+    hot_reload: bool,
+}
 
 impl FilesystemConfig {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            mounts: Vec::new(),
+            app_id: None,
+            user_dir: None,
+            user_prefix: DEFAULT_USER_PREFIX.to_owned(),
+            hot_reload: false,
+        }
+    }
+
+    pub fn app_id(mut self, app_id: Option<impl Into<String>>) -> Self {
+        self.app_id = app_id.map(|app_id| app_id.into());
+        self
+    }
+
+    pub fn user_dir(mut self, path: Option<impl Into<PathBuf>>) -> Self {
+        self.user_dir = path.map(|path| path.into());
+        self
+    }
+
+    pub fn user_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.user_prefix = normalize_path(prefix.as_ref()).unwrap_or_default();
+        self
+    }
+
+    pub fn mount(mut self, mount: Mount) -> Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    pub fn hot_reload(mut self, enabled: bool) -> Self {
+        self.hot_reload = enabled;
+        self
+    }
+
+    pub(crate) fn is_hot_reload_enabled(&self) -> bool {
+        self.hot_reload
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filesystem, FilesystemConfig, Mount, normalize_path};
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("assets/./images/../bunny.png").as_deref(), Some("assets/bunny.png"));
+        assert_eq!(normalize_path("/assets\\bunny.png").as_deref(), Some("assets/bunny.png"));
+        assert_eq!(normalize_path("../bunny.png"), None);
+    }
+
+    #[test]
+    fn test_mount_priority() {
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::memory([("a.txt", "base"), ("b.txt", "base")]).prefix("data"))
+            .mount(Mount::memory([("a.txt", "patch")]).prefix("data"))
+            .mount(Mount::memory([("b.txt", "mod")]).prefix("data").priority(-1))
+            .mount(Mount::memory([("c/d.txt", "mod")]).prefix("mods/foo"))
+        ).unwrap();
+        assert_eq!(filesystem.read_to_string("data/a.txt").unwrap(), "patch");
+        assert!(filesystem.modified("data/a.txt").is_none());
+        assert_eq!(filesystem.read_to_string("/data/b.txt").unwrap(), "base");
+        assert_eq!(filesystem.read_to_string("mods/foo/c/d.txt").unwrap(), "mod");
+        assert!(filesystem.read("a.txt").is_err());
+        assert!(filesystem.exists("mods/foo/c"));
+        assert_eq!(filesystem.read_dir("data").unwrap(), vec!["a.txt", "b.txt"]);
+        assert_eq!(filesystem.read_dir("mods").unwrap(), vec!["foo"]);
+        assert_eq!(filesystem.read_dir("").unwrap(), vec!["data", "mods"]);
+    }
+
+    #[test]
+    fn test_host_path() {
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::memory([("Cargo.toml", "")]))
+        ).unwrap();
+        let dir_name = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).file_name().unwrap();
+        let path = std::path::Path::new("..").join(dir_name).join("Cargo.toml");
+        assert!(filesystem.exists(&path));
+        assert!(filesystem.modified(&path).is_some());
+        assert_eq!(filesystem.read(&path).unwrap(), std::fs::read("Cargo.toml").unwrap());
+        assert!(filesystem.read("../missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_user_dir() {
+        let user_dir = std::env::temp_dir().join(format!("tge-test-user-{}", std::process::id()));
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::memory([("user/settings.txt", "default")]))
+            .user_dir(Some(&user_dir))
+        ).unwrap();
+        assert_eq!(filesystem.read_to_string("user/settings.txt").unwrap(), "default");
+        assert!(filesystem.modified("user/settings.txt").is_none());
+        filesystem.write("user/settings.txt", "custom").unwrap();
+        assert_eq!(filesystem.read_to_string("user/settings.txt").unwrap(), "custom");
+        assert!(filesystem.modified("user/settings.txt").is_some());
+        filesystem.append("user/saves/slot1.txt", "a").unwrap();
+        filesystem.append("user/saves/slot1.txt", "b").unwrap();
+        assert_eq!(filesystem.read_to_string("user/saves/slot1.txt").unwrap(), "ab");
+        filesystem.create_dir("user/screenshots").unwrap();
+        assert_eq!(filesystem.read_dir("user").unwrap(), vec!["saves", "screenshots", "settings.txt"]);
+        assert!(filesystem.write("assets/bunny.png", "").is_err());
+        assert!(filesystem.write("user/../assets/bunny.png", "").is_err());
+        assert!(filesystem.remove("user").is_err());
+        filesystem.remove("user/saves").unwrap();
+        assert!(!filesystem.exists("user/saves/slot1.txt"));
+        std::fs::remove_dir_all(&user_dir).unwrap();
+    }
+
+    #[test]
+    fn test_embedded_mount() {
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::embedded(embed_files!["assets/bunny.png", "assets/ark-pixel-font/OFL.txt"]))
+        ).unwrap();
+        assert_eq!(filesystem.read("assets/bunny.png").unwrap(), include_bytes!("../assets/bunny.png"));
+        assert!(filesystem.exists("assets/ark-pixel-font"));
+        assert_eq!(filesystem.read_dir("assets").unwrap(), vec!["ark-pixel-font", "bunny.png"]);
+    }
+
+    #[test]
+    fn test_archive_mount() {
+        use std::io::Write;
+        use zip::ZipWriter;
+        use zip::write::SimpleFileOptions;
+
+        let path = std::env::temp_dir().join(format!("tge-test-archive-{}.zip", std::process::id()));
+        {
+            let mut writer = ZipWriter::new(std::fs::File::create(&path).unwrap());
+            writer.add_directory("images/", SimpleFileOptions::default()).unwrap();
+            writer.start_file("images/bunny.png", SimpleFileOptions::default()).unwrap();
+            writer.write_all(b"bunny").unwrap();
+            writer.start_file("readme.txt", SimpleFileOptions::default()).unwrap();
+            writer.write_all(b"readme").unwrap();
+            writer.finish().unwrap();
+        }
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::archive(&path).prefix("assets"))
+        ).unwrap();
+        assert_eq!(filesystem.read("assets/images/bunny.png").unwrap(), b"bunny");
+        assert_eq!(filesystem.read_to_string("assets/readme.txt").unwrap(), "readme");
+        assert!(filesystem.read("assets/images").is_err());
+        assert!(filesystem.exists("assets/images"));
+        assert_eq!(filesystem.read_dir("assets").unwrap(), vec!["images", "readme.txt"]);
+        drop(filesystem);
+        std::fs::remove_file(&path).unwrap();
     }
 }
@@ -0,0 +1,50 @@
+use super::root::{Root, not_found, is_parent_dir, list_dir};
+use crate::error::{GameError, GameResult};
+use zip::ZipArchive;
+use zip::result::ZipError;
+use std::fs::File;
+use std::io::{self, Read, BufReader};
+use std::path::Path;
+use std::sync::Mutex;
+
+pub(crate) struct ArchiveRoot {
+    archive: Mutex<ZipArchive<BufReader<File>>>,
+    names: Vec<String>,
+}
+
+impl ArchiveRoot {
+    pub fn open(path: &Path) -> GameResult<Self> {
+        let file = File::open(path).map_err(|error| GameError::IoError(error.into()))?;
+        let archive = ZipArchive::new(BufReader::new(file)).map_err(|error| GameError::InitError(error.into()))?;
+        let names = archive.file_names()
+            .filter_map(super::normalize_path)
+            .filter(|name| !name.is_empty())
+            .collect();
+        Ok(Self {
+            archive: Mutex::new(archive),
+            names,
+        })
+    }
+}
+
+impl Root for ArchiveRoot {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let mut archive = self.archive.lock().unwrap_or_else(|error| error.into_inner());
+        let mut file = match archive.by_name(path) {
+            Ok(file) if file.is_file() => file,
+            Ok(_) | Err(ZipError::FileNotFound) => return Err(not_found(path)),
+            Err(error) => return Err(io::Error::other(error)),
+        };
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.names.iter().any(|name| name == path) || is_parent_dir(self.names.iter().map(|name| name.as_str()), path)
+    }
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        list_dir(self.names.iter().map(|name| name.as_str()), path)
+    }
+}
@@ -0,0 +1,121 @@
+use super::root::{Root, DirectoryRoot, MemoryRoot, EmbeddedRoot};
+use super::archive::ArchiveRoot;
+use crate::error::GameResult;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Clone)]
+enum MountSource {
+    Directory(PathBuf),
+    Archive(PathBuf),
+    Memory(Arc<HashMap<String, Arc<[u8]>>>),
+    Embedded(&'static [(&'static str, &'static [u8])]),
+}
+
+impl fmt::Debug for MountSource {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Directory(path) => fmt.debug_tuple("Directory").field(path).finish(),
+            Self::Archive(path) => fmt.debug_tuple("Archive").field(path).finish(),
+            Self::Memory(files) => fmt.debug_tuple("Memory").field(&files.keys().collect::<Vec<_>>()).finish(),
+            Self::Embedded(files) => fmt.debug_tuple("Embedded").field(&files.iter().map(|(name, _)| name).collect::<Vec<_>>()).finish(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Mount {
+    source: MountSource,
+    prefix: String,
+    priority: i32,
+}
+
+impl Mount {
+    fn new(source: MountSource) -> Self {
+        Self {
+            source,
+            prefix: String::new(),
+            priority: 0,
+        }
+    }
+
+    pub fn directory(path: impl Into<PathBuf>) -> Self {
+        Self::new(MountSource::Directory(path.into()))
+    }
+
+    pub fn archive(path: impl Into<PathBuf>) -> Self {
+        Self::new(MountSource::Archive(path.into()))
+    }
+
+    pub fn memory<K, V>(files: impl IntoIterator<Item = (K, V)>) -> Self
+        where
+            K: AsRef<str>,
+            V: Into<Vec<u8>>,
+    {
+        let files = files.into_iter()
+            .filter_map(|(name, bytes)| {
+                super::normalize_path(name.as_ref()).map(|name| (name, bytes.into().into()))
+            })
+            .collect();
+        Self::new(MountSource::Memory(Arc::new(files)))
+    }
+
+    pub fn embedded(files: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self::new(MountSource::Embedded(files))
+    }
+
+    pub fn prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.prefix = super::normalize_path(prefix.as_ref()).unwrap_or_default();
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub(crate) fn open(&self) -> GameResult<MountedRoot> {
+        let root: Box<dyn Root> = match &self.source {
+            MountSource::Directory(path) => Box::new(DirectoryRoot::new(path.clone())),
+            MountSource::Archive(path) => Box::new(ArchiveRoot::open(path)?),
+            MountSource::Memory(files) => Box::new(MemoryRoot::new(files.clone())),
+            MountSource::Embedded(files) => Box::new(EmbeddedRoot::new(files)),
+        };
+        Ok(MountedRoot {
+            prefix: self.prefix.clone(),
+            priority: self.priority,
+            root,
+        })
+    }
+}
+
+pub(crate) struct MountedRoot {
+    prefix: String,
+    priority: i32,
+    root: Box<dyn Root>,
+}
+
+impl MountedRoot {
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn root(&self) -> &dyn Root {
+        self.root.as_ref()
+    }
+
+    pub fn child_of(&self, path: &str) -> Option<&str> {
+        let rest = if path.is_empty() {
+            Some(self.prefix.as_str())
+        } else {
+            self.prefix.strip_prefix(path).and_then(|rest| rest.strip_prefix('/'))
+        };
+        rest.and_then(|rest| rest.split('/').next()).filter(|entry| !entry.is_empty())
+    }
+
+    pub fn resolve<'a>(&self, path: &'a str) -> Option<&'a str> {
+        super::strip_path_prefix(path, &self.prefix)
+    }
+}
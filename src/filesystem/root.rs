@@ -0,0 +1,133 @@
+use std::collections::{HashMap, BTreeSet};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+pub(crate) trait Root: Send + Sync {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    fn exists(&self, path: &str) -> bool;
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>>;
+
+    fn modified(&self, _path: &str) -> Option<SystemTime> {
+        None
+    }
+}
+
+pub(crate) fn not_found(path: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("`{}` not found", path))
+}
+
+pub(crate) struct DirectoryRoot {
+    path: PathBuf,
+}
+
+impl DirectoryRoot {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Root for DirectoryRoot {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        std::fs::read(self.path.join(path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.path.join(path).exists()
+    }
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(self.path.join(path))? {
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+        Ok(names)
+    }
+
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        std::fs::metadata(self.path.join(path)).and_then(|metadata| metadata.modified()).ok()
+    }
+}
+
+pub(crate) struct MemoryRoot {
+    files: Arc<HashMap<String, Arc<[u8]>>>,
+}
+
+impl MemoryRoot {
+    pub fn new(files: Arc<HashMap<String, Arc<[u8]>>>) -> Self {
+        Self { files }
+    }
+}
+
+impl Root for MemoryRoot {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.files.get(path)
+            .map(|bytes| bytes.to_vec())
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path) || is_parent_dir(self.files.keys().map(|name| name.as_str()), path)
+    }
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        list_dir(self.files.keys().map(|name| name.as_str()), path)
+    }
+}
+
+pub(crate) struct EmbeddedRoot {
+    files: HashMap<String, &'static [u8]>,
+}
+
+impl EmbeddedRoot {
+    pub fn new(files: &'static [(&'static str, &'static [u8])]) -> Self {
+        let files = files.iter()
+            .filter_map(|(name, bytes)| super::normalize_path(name).map(|name| (name, *bytes)))
+            .collect();
+        Self { files }
+    }
+}
+
+impl Root for EmbeddedRoot {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.files.get(path)
+            .map(|bytes| bytes.to_vec())
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path) || is_parent_dir(self.files.keys().map(|name| name.as_str()), path)
+    }
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        list_dir(self.files.keys().map(|name| name.as_str()), path)
+    }
+}
+
+pub(crate) fn is_parent_dir<'a>(names: impl Iterator<Item = &'a str>, path: &str) -> bool {
+    path.is_empty() || names.into_iter().any(|name| {
+        name.strip_prefix(path).is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+pub(crate) fn list_dir<'a>(names: impl Iterator<Item = &'a str>, path: &str) -> io::Result<Vec<String>> {
+    let mut entries = BTreeSet::new();
+    for name in names {
+        let rest = if path.is_empty() {
+            Some(name)
+        } else {
+            name.strip_prefix(path).and_then(|rest| rest.strip_prefix('/'))
+        };
+        if let Some(entry) = rest.and_then(|rest| rest.split('/').next()).filter(|entry| !entry.is_empty()) {
+            entries.insert(entry.to_owned());
+        }
+    }
+    if entries.is_empty() {
+        Err(not_found(path))
+    } else {
+        Ok(entries.into_iter().collect())
+    }
+}
@@ -0,0 +1,71 @@
+use super::Filesystem;
+use crate::error::GameResult;
+use crate::engine::Engine;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(crate) trait Reload {
+    fn is_alive(&self) -> bool;
+
+    fn reload(&self, engine: &mut Engine) -> GameResult;
+}
+
+struct Watch {
+    paths: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+    reload: Box<dyn Reload>,
+}
+
+pub(crate) struct Watcher {
+    interval: Duration,
+    last_poll: Instant,
+    watches: Vec<Watch>,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self {
+            interval: POLL_INTERVAL,
+            last_poll: Instant::now(),
+            watches: Vec::new(),
+        }
+    }
+
+    pub fn watch(&mut self, filesystem: &Filesystem, paths: Vec<PathBuf>, reload: impl Reload + 'static) {
+        let modified = paths.iter()
+            .map(|path| filesystem.modified(path))
+            .collect::<Vec<_>>();
+        if modified.iter().any(|modified| modified.is_some()) {
+            self.watches.push(Watch {
+                paths,
+                modified,
+                reload: Box::new(reload),
+            });
+        }
+    }
+
+    pub fn poll(&mut self, engine: &mut Engine) -> Vec<(PathBuf, GameResult)> {
+        let mut reloaded = Vec::new();
+        if self.last_poll.elapsed() < self.interval {
+            return reloaded;
+        }
+        self.last_poll = Instant::now();
+        self.watches.retain(|watch| watch.reload.is_alive());
+        for watch in self.watches.iter_mut() {
+            let mut changed = None;
+            for (path, modified) in watch.paths.iter().zip(watch.modified.iter_mut()) {
+                let current = engine.filesystem().modified(path);
+                if current != *modified {
+                    *modified = current;
+                    changed.get_or_insert(path);
+                }
+            }
+            if let Some(path) = changed {
+                reloaded.push((path.clone(), watch.reload.reload(engine)));
+            }
+        }
+        reloaded
+    }
+}
@@ -3,29 +3,46 @@ mod opengl;
 mod program;
 mod color;
 mod vertex;
+mod vertex_format;
+mod elements;
 #[allow(dead_code)]
 mod renderer;
+mod sprite_renderer;
 mod image;
 mod texture;
 mod canvas;
 mod font;
 mod texture_ref;
 mod params;
+mod mask;
+mod mesh;
+mod stats;
+mod sort;
 
 use opengl::BufferUsage;
 use renderer::{Renderer, RendererBuilder};
+use sprite_renderer::SpriteRenderer;
+use sort::{DeferredDraw, DeferredGeometry, Recording};
+use mesh::MeshBuffers;
 
-pub use opengl::{PrimitiveType, FilterMode, Filter, WrapMode, Wrap};
+pub use opengl::{UniformValue, PrimitiveType, FilterMode, Filter, WrapMode, Wrap, BlendFactor, BlendEquation, BlendComponent, BlendMode};
 pub use program::Program;
 pub use color::Color;
 pub use vertex::Vertex;
+pub use vertex_format::{AttributeType, VertexAttribute, VertexFormat, VertexData};
+pub use elements::Elements;
 pub use self::image::Image;
 pub(crate) use self::image::validate_pixels;
+pub(crate) use font::parse_font;
 pub use texture::Texture;
 pub use canvas::Canvas;
 pub use font::Font;
 pub use texture_ref::TextureRef;
-pub use params::{MeshDrawParams, SpriteDrawParams, TextLayoutGravity, TextDrawParams};
+pub use params::{MeshDrawParams, StaticMeshDrawParams, SpriteDrawParams, TextLayoutGravity, TextDrawParams};
+pub use mask::MaskMode;
+pub use mesh::Mesh;
+pub use stats::DrawStats;
+pub use sort::SortMode;
 
 use crate::error::{GameError, GameResult};
 use crate::math::{Position, Size, Region, Viewport, Transform};
@@ -34,7 +51,13 @@ use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
 use glutin::{ContextWrapper, PossiblyCurrent};
 use glow::{Context, HasContext};
 use glam::{Vec4, Mat4};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::collections::HashMap;
+
+const MAX_MASK_DEPTH: usize = 8;
+const MASK_ALPHA_TEST: f32 = 0.5;
+
+const BATCH_MAX_VERTEX_COUNT: usize = u16::MAX as usize + 1;
 
 const SPRITE_VERTEX_COUNT: usize = 4;
 const SPRITE_ELEMENT_COUNT: usize = 6;
@@ -43,10 +66,12 @@ const SPRITE_ELEMENTS: [u16; SPRITE_ELEMENT_COUNT] = [
     1, 2, 3
 ];
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 struct DrawCommand {
     pub texture: Rc<opengl::Texture>,
     pub primitive: PrimitiveType,
+    pub blend_mode: BlendMode,
+    pub instanced: bool,
 }
 
 pub struct Graphics {
@@ -57,17 +82,35 @@ pub struct Graphics {
     projection_matrix: Mat4,
     transform_matrix: Mat4,
     transform_stack: Vec<Mat4>,
+    scissor_stack: Vec<Region>,
+    mask_stack: Vec<MaskMode>,
+    mask_writing: bool,
     default_program: Rc<opengl::Program>,
     program: Rc<opengl::Program>,
+    instanced_program: Rc<opengl::Program>,
     default_filter: Filter,
     default_wrap: Wrap,
     default_texture: Rc<opengl::Texture>,
+    blend_mode: BlendMode,
+    applied_blend_mode: BlendMode,
+    applied_texture: Weak<opengl::Texture>,
     canvas: Option<Rc<opengl::Framebuffer>>,
     max_texture_size: u32,
     renderer: Renderer,
+    wide_renderer: Renderer<u32>,
+    format_renderers: HashMap<VertexFormat, Renderer<u32>>,
     vertices: Vec<Vertex>,
     elements: Vec<u16>,
+    sprite_instancing: bool,
+    sprite_renderer: SpriteRenderer,
+    instances: Vec<f32>,
     draw_command: DrawCommand,
+    sort_mode: SortMode,
+    layer: i32,
+    recording: Recording<opengl::Program, DeferredDraw>,
+    replaying: bool,
+    stats: DrawStats,
+    frame_stats: DrawStats,
 }
 
 impl Graphics {
@@ -88,8 +131,12 @@ impl Graphics {
         let program = default_program.clone();
         program.bind();
         program.set_uniform_matrix_4("u_projection", &projection_matrix.to_cols_array());
+        program.set_uniform_1_f32("u_alpha_test", 0.0);
+
+        let instanced_program = Program::instanced(gl.clone())?;
 
         let default_texture = Texture::white_1_1(gl.clone())?;
+        let applied_texture = Rc::downgrade(&default_texture);
 
         let max_texture_size = unsafe {
             gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) as u32
@@ -99,18 +146,26 @@ impl Graphics {
             .init_vertex_size(BufferUsage::Stream, graphics_config.renderer_vertex_size)
             .init_element_size(BufferUsage::Stream, graphics_config.renderer_element_size)
             .build()?;
+        let wide_renderer = RendererBuilder::new(gl.clone())?
+            .init_vertex_size(BufferUsage::Stream, graphics_config.renderer_vertex_size)
+            .init_element_size(BufferUsage::Stream, graphics_config.renderer_element_size)
+            .build()?;
         let vertices = Vec::with_capacity(graphics_config.renderer_vertex_size);
         let elements = Vec::with_capacity(graphics_config.renderer_element_size);
+        let sprite_renderer = SpriteRenderer::new(gl.clone(), &SPRITE_ELEMENTS, graphics_config.renderer_instance_size)?;
+        let instances = Vec::with_capacity(sprite_renderer::INSTANCE_STRIDE * graphics_config.renderer_instance_size);
 
         let draw_command = DrawCommand {
             texture: default_texture.clone(),
             primitive: PrimitiveType::Triangles,
+            blend_mode: BlendMode::default(),
+            instanced: false,
         };
 
         unsafe {
             gl.enable(glow::BLEND);
-            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
         }
+        BlendMode::default().apply(&gl);
 
         Ok(Self {
             context_wrapper,
@@ -120,17 +175,35 @@ impl Graphics {
             projection_matrix,
             transform_matrix,
             transform_stack,
+            scissor_stack: Vec::new(),
+            mask_stack: Vec::new(),
+            mask_writing: false,
             default_program,
             program,
+            instanced_program,
             default_filter: graphics_config.default_filter,
             default_wrap: graphics_config.default_wrap,
             default_texture,
+            blend_mode: BlendMode::default(),
+            applied_blend_mode: BlendMode::default(),
+            applied_texture,
             canvas: None,
             max_texture_size,
             renderer,
+            wide_renderer,
+            format_renderers: HashMap::new(),
             vertices,
             elements,
+            sprite_instancing: graphics_config.sprite_instancing,
+            sprite_renderer,
+            instances,
             draw_command,
+            sort_mode: SortMode::default(),
+            layer: 0,
+            recording: Recording::new(),
+            replaying: false,
+            stats: DrawStats::default(),
+            frame_stats: DrawStats::default(),
         })
     }
 
@@ -145,23 +218,69 @@ impl Graphics {
             }
             self.projection_matrix = Mat4::orthographic_rh_gl(0.0, logical_size.width, logical_size.height, 0.0, -1.0, 1.0);
             self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
+            self.apply_scissor();
         }
     }
 
     pub fn flush(&mut self) {
+        self.submit_deferred_draws();
+        self.flush_batch();
+    }
+
+    fn flush_batch(&mut self) {
         if !self.vertices.is_empty() && !self.elements.is_empty() {
             self.renderer.update_vertices(0, &self.vertices);
             self.renderer.update_elements(0, &self.elements);
-            self.draw_command.texture.bind();
+            self.apply_blend_mode();
+            self.apply_texture();
+            self.program.bind_textures();
             self.renderer.draw_elements(self.draw_command.primitive, self.elements.len(), 0);
+            self.program.unbind_textures();
+            self.draw_command.texture.unbind();
+            self.frame_stats.draw_calls += 1;
+            self.frame_stats.vertices += self.vertices.len();
+            self.frame_stats.elements += self.elements.len();
+        }
+        if !self.instances.is_empty() {
+            self.sprite_renderer.update_instances(0, &self.instances);
+            self.apply_blend_mode();
+            // Only the default program is instanced and it is not exposed for user uniforms or samplers,
+            // so the projection and alpha test are all the state the instanced program has to mirror.
+            self.instanced_program.bind();
+            self.instanced_program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
+            self.instanced_program.set_uniform_1_f32("u_alpha_test", if self.mask_writing { MASK_ALPHA_TEST } else { 0.0 });
+            self.apply_texture();
+            let instance_count = self.instances.len() / sprite_renderer::INSTANCE_STRIDE;
+            self.sprite_renderer.draw_instances(instance_count);
             self.draw_command.texture.unbind();
+            self.program.bind();
+            self.frame_stats.draw_calls += 1;
+            self.frame_stats.instances += instance_count;
         }
         self.vertices.clear();
         self.elements.clear();
+        self.instances.clear();
+    }
+
+    /// Binds the draw texture, counting a switch when it differs from the one of the previous draw call.
+    fn apply_texture(&mut self) {
+        if self.applied_texture.as_ptr() != Rc::as_ptr(&self.draw_command.texture) {
+            self.frame_stats.texture_switches += 1;
+            self.applied_texture = Rc::downgrade(&self.draw_command.texture);
+        }
+        self.draw_command.texture.bind();
+    }
+
+    fn apply_blend_mode(&mut self) {
+        if self.applied_blend_mode != self.draw_command.blend_mode {
+            self.draw_command.blend_mode.apply(&self.gl);
+            self.applied_blend_mode = self.draw_command.blend_mode;
+        }
     }
 
     pub(crate) fn present(&mut self) -> GameResult {
         self.flush();
+        self.stats = self.frame_stats.finish_frame();
         self.context_wrapper.swap_buffers()
             .map_err(|error| GameError::RuntimeError(error.into()))
     }
@@ -196,41 +315,85 @@ impl Graphics {
         if self.viewport != viewport {
             self.flush();
             self.viewport = viewport;
+            let gl_viewport = self.to_gl_region(self.viewport);
+            unsafe {
+                self.gl.viewport(gl_viewport.x, gl_viewport.y, gl_viewport.width, gl_viewport.height);
+            }
             if self.canvas.is_some() {
-                unsafe {
-                    self.gl.viewport(
-                        self.viewport.x.round() as i32,
-                        self.viewport.y.round() as i32,
-                        self.viewport.width.round() as i32,
-                        self.viewport.height.round() as i32,
-                    );
-                }
                 self.projection_matrix = Mat4::orthographic_rh_gl(0.0, self.viewport.width, 0.0, self.viewport.height, -1.0, 1.0);
             } else {
-                let scale_factor = self.window().scale_factor();
-                let physical_viewport = {
-                    let physical_position = LogicalPosition::new(self.viewport.x, self.viewport.y).to_physical::<i32>(scale_factor);
-                    let physical_size = LogicalSize::new(self.viewport.width, self.viewport.height).to_physical::<i32>(scale_factor);
-                    Viewport::new(physical_position.x, physical_position.y, physical_size.width, physical_size.height)
-                };
-                let physical_size = {
-                    let physical_size = LogicalSize::new(self.size.width, self.size.height).to_physical::<i32>(scale_factor);
-                    Size::new(physical_size.width, physical_size.height)
-                };
-                unsafe {
-                    self.gl.viewport(
-                        physical_viewport.x,
-                        physical_size.height - physical_viewport.y - physical_viewport.height,
-                        physical_viewport.width,
-                        physical_viewport.height,
-                    );
-                }
                 self.projection_matrix = Mat4::orthographic_rh_gl(0.0, self.viewport.width, self.viewport.height, 0.0, -1.0, 1.0);
             }
             self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
         }
     }
 
+    fn to_gl_region(&self, region: Region) -> Region<i32> {
+        if self.canvas.is_some() {
+            Region::new(
+                region.x.round() as i32,
+                region.y.round() as i32,
+                region.width.round() as i32,
+                region.height.round() as i32,
+            )
+        } else {
+            let scale_factor = self.window().scale_factor();
+            let physical_region = {
+                let physical_position = LogicalPosition::new(region.x, region.y).to_physical::<i32>(scale_factor);
+                let physical_size = LogicalSize::new(region.width, region.height).to_physical::<i32>(scale_factor);
+                Region::new(physical_position.x, physical_position.y, physical_size.width, physical_size.height)
+            };
+            let physical_size = {
+                let physical_size = LogicalSize::new(self.size.width, self.size.height).to_physical::<i32>(scale_factor);
+                Size::new(physical_size.width, physical_size.height)
+            };
+            Region::new(
+                physical_region.x,
+                physical_size.height - physical_region.y - physical_region.height,
+                physical_region.width,
+                physical_region.height,
+            )
+        }
+    }
+
+    pub fn scissor(&self) -> Option<Region> {
+        self.scissor_stack.last().copied()
+    }
+
+    pub fn push_scissor(&mut self, region: impl Into<Region>) {
+        let region = region.into();
+        let region = match self.scissor_stack.last() {
+            Some(scissor) => scissor.intersection(&region),
+            None => region,
+        };
+        self.flush();
+        self.scissor_stack.push(region);
+        self.apply_scissor();
+    }
+
+    pub fn pop_scissor(&mut self) {
+        if !self.scissor_stack.is_empty() {
+            self.flush();
+            self.scissor_stack.pop();
+            self.apply_scissor();
+        }
+    }
+
+    fn apply_scissor(&self) {
+        match self.scissor_stack.last() {
+            Some(scissor) => {
+                let gl_scissor = self.to_gl_region(*scissor);
+                unsafe {
+                    self.gl.enable(glow::SCISSOR_TEST);
+                    self.gl.scissor(gl_scissor.x, gl_scissor.y, gl_scissor.width.max(0), gl_scissor.height.max(0));
+                }
+            }
+            None => unsafe {
+                self.gl.disable(glow::SCISSOR_TEST);
+            },
+        }
+    }
+
     pub fn set_transform(&mut self, transform: impl Into<Transform>) {
         self.transform_matrix = transform.into().0;
     }
@@ -253,13 +416,51 @@ impl Graphics {
         let program = program.map(|program| program.program().clone())
             .unwrap_or_else(|| self.default_program.clone());
         if self.program != program {
-            self.flush();
+            if self.recording.is_stale() {
+                self.submit_deferred_draws();
+            }
+            self.flush_batch();
+            // Recorded draws count their program switches when they are replayed.
+            if !self.is_recording() {
+                self.frame_stats.program_switches += 1;
+            }
             self.program = program;
-            self.program.bind();
-            self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
+            self.rebind_program();
+        }
+    }
+
+    pub(crate) fn rebind_program(&mut self) {
+        self.program.bind();
+        self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
+        self.program.set_uniform_1_f32("u_alpha_test", if self.mask_writing { MASK_ALPHA_TEST } else { 0.0 });
+        // Replayed draws keep the uniforms they are recorded with, changes are applied by the next draw.
+        if !self.replaying {
+            self.frame_stats.texture_switches += self.program.apply_uniforms();
         }
     }
 
+    pub fn sprite_instancing(&self) -> bool {
+        self.sprite_instancing
+    }
+
+    /// Sprites drawn with the default program are expanded on the GPU from per-instance data when enabled,
+    /// sprites drawn with a custom program always take the batched path.
+    pub fn set_sprite_instancing(&mut self, sprite_instancing: bool) {
+        self.sprite_instancing = sprite_instancing;
+    }
+
+    fn is_sprite_instancing(&self) -> bool {
+        self.sprite_instancing && Rc::ptr_eq(&self.program, &self.default_program)
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
     pub fn default_filter(&self) -> Filter {
         self.default_filter
     }
@@ -283,6 +484,7 @@ impl Graphics {
         };
         if self.canvas != canvas {
             self.flush();
+            self.frame_stats.canvas_switches += 1;
             if canvas.is_none() {
                 if let Some(canvas) = &self.canvas {
                     canvas.unbind();
@@ -311,11 +513,75 @@ impl Graphics {
                 self.projection_matrix = Mat4::orthographic_rh_gl(0.0, logical_size.width, logical_size.height, 0.0, -1.0, 1.0);
             }
             self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
+            self.apply_scissor();
+        }
+    }
+
+    pub fn begin_mask(&mut self) {
+        assert!(!self.mask_writing, "mask is already begun");
+        assert!(self.mask_stack.len() < MAX_MASK_DEPTH, "too many nested masks");
+        self.flush();
+        let bit = 1u32 << self.mask_stack.len();
+        unsafe {
+            self.gl.enable(glow::STENCIL_TEST);
+            self.gl.stencil_mask(bit);
+            self.gl.clear_stencil(0);
+            self.gl.clear(glow::STENCIL_BUFFER_BIT);
+            self.gl.stencil_func(glow::ALWAYS, bit as i32, bit);
+            self.gl.stencil_op(glow::KEEP, glow::KEEP, glow::REPLACE);
+            self.gl.color_mask(false, false, false, false);
+        }
+        self.mask_writing = true;
+        self.program.set_uniform_1_f32("u_alpha_test", MASK_ALPHA_TEST);
+    }
+
+    pub fn end_mask(&mut self, mode: MaskMode) {
+        assert!(self.mask_writing, "mask is not begun");
+        self.flush();
+        unsafe {
+            self.gl.stencil_mask(0);
+            self.gl.color_mask(true, true, true, true);
+        }
+        self.mask_writing = false;
+        self.program.set_uniform_1_f32("u_alpha_test", 0.0);
+        self.mask_stack.push(mode);
+        self.apply_mask();
+    }
+
+    pub fn pop_mask(&mut self) {
+        if !self.mask_stack.is_empty() {
+            self.flush();
+            self.mask_stack.pop();
+            self.apply_mask();
+        }
+    }
+
+    fn apply_mask(&self) {
+        if self.mask_stack.is_empty() {
+            unsafe {
+                self.gl.disable(glow::STENCIL_TEST);
+            }
+            return;
+        }
+        let mut reference = 0;
+        let mut mask = 0;
+        for (level, mode) in self.mask_stack.iter().enumerate() {
+            let bit = 1u32 << level;
+            mask |= bit;
+            if *mode == MaskMode::Inside {
+                reference |= bit;
+            }
+        }
+        unsafe {
+            self.gl.enable(glow::STENCIL_TEST);
+            self.gl.stencil_func(glow::EQUAL, reference as i32, mask);
+            self.gl.stencil_op(glow::KEEP, glow::KEEP, glow::KEEP);
         }
     }
 
     pub fn clear(&mut self, color: impl Into<Color>) {
         let color = color.into();
+        self.flush();
         unsafe {
             self.gl.clear_color(color.red, color.green, color.blue, color.alpha);
             self.gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
@@ -323,41 +589,206 @@ impl Graphics {
     }
 
     fn switch_draw_command(&mut self, draw_command: DrawCommand) {
+        if !self.replaying {
+            // Uniforms live in the program, so recorded draws go out before a change reaches it.
+            if self.recording.is_stale() {
+                self.submit_deferred_draws();
+            }
+            if self.program.has_dirty_uniforms() {
+                if self.has_pending_batch() {
+                    self.frame_stats.uniform_flushes += 1;
+                }
+                self.flush_batch();
+                self.frame_stats.texture_switches += self.program.apply_uniforms();
+            }
+        }
         if self.draw_command != draw_command {
-            self.flush();
+            self.flush_batch();
+            if !self.is_recording() {
+                if self.draw_command.primitive != draw_command.primitive {
+                    self.frame_stats.primitive_switches += 1;
+                }
+                if self.draw_command.blend_mode != draw_command.blend_mode {
+                    self.frame_stats.blend_mode_switches += 1;
+                }
+            }
             self.draw_command = draw_command;
         }
     }
 
-    fn append_vertices_and_elements(&mut self, vertices: Vec<Vertex>, elements: Option<Vec<u16>>) {
-        let mut elements = elements.unwrap_or_else(|| (0..vertices.len() as u16).collect());
-        if self.renderer.vertex_size() < self.vertices.len() + vertices.len() || self.renderer.element_size() < self.elements.len() + elements.len() {
-            self.flush();
+    fn has_pending_batch(&self) -> bool {
+        !self.elements.is_empty() || !self.instances.is_empty()
+    }
+
+    /// Counters of the last presented frame.
+    pub fn stats(&self) -> DrawStats {
+        self.stats
+    }
+
+    fn append_vertices_and_elements<E: Copy + Into<u32>>(&mut self, vertices: Vec<Vertex>, elements: Option<&[E]>, layer: i32) {
+        if self.is_recording() {
+            let elements = match elements {
+                Some(elements) => elements.iter().map(|element| (*element).into()).collect(),
+                None => (0..vertices.len() as u32).collect(),
+            };
+            self.record(DeferredGeometry::Vertices(vertices, elements), layer);
+            return;
         }
-        assert!(self.renderer.vertex_size() >= self.vertices.len() + vertices.len(), "no enough renderer vertex size");
-        assert!(self.renderer.element_size() >= self.elements.len() + elements.len(), "no enough renderer element size");
-        let append_vertex_count = vertices.len() as u16;
-        let element_offset = self.vertices.len() as u16;
-        for element in &mut elements {
-            assert!(*element < append_vertex_count, "element must < append vertex count");
-            *element += element_offset;
+        let append_vertex_count = vertices.len();
+        let append_element_count = elements.map_or(append_vertex_count, |elements| elements.len());
+        if append_vertex_count > self.renderer.vertex_size().min(BATCH_MAX_VERTEX_COUNT)
+            || append_element_count > self.renderer.element_size() {
+            self.flush_batch();
+            self.draw_wide_vertices(&vertices, elements);
+            return;
+        }
+        if self.renderer.vertex_size() < self.vertices.len() + append_vertex_count
+            || self.renderer.element_size() < self.elements.len() + append_element_count
+            || BATCH_MAX_VERTEX_COUNT < self.vertices.len() + append_vertex_count {
+            if self.has_pending_batch() {
+                self.frame_stats.capacity_flushes += 1;
+            }
+            self.flush_batch();
+        }
+        let element_offset = self.vertices.len();
+        match elements {
+            Some(elements) => {
+                for element in elements {
+                    let element = (*element).into() as usize;
+                    assert!(element < append_vertex_count, "element must < append vertex count");
+                    self.elements.push((element + element_offset) as u16);
+                }
+            }
+            None => self.elements.extend((0..append_vertex_count).map(|element| (element + element_offset) as u16)),
         }
         self.vertices.extend(vertices);
-        self.elements.extend(elements);
+    }
+
+    /// Draws that do not fit in an empty batch skip it and are drawn immediately with `u32` elements.
+    fn draw_wide_vertices<E: Copy + Into<u32>>(&mut self, vertices: &[Vertex], elements: Option<&[E]>) {
+        let elements = match elements {
+            Some(elements) => elements.iter().map(|element| (*element).into()).collect::<Vec<u32>>(),
+            None => (0..vertices.len() as u32).collect(),
+        };
+        assert!(elements.iter().all(|element| (*element as usize) < vertices.len()), "element must < append vertex count");
+        let renderer = &mut self.wide_renderer;
+        if renderer.vertex_size() < vertices.len() {
+            renderer.init_vertex_size(BufferUsage::Stream, vertices.len());
+        }
+        if renderer.element_size() < elements.len() {
+            renderer.init_element_size(BufferUsage::Stream, elements.len());
+        }
+        renderer.update_vertices(0, vertices);
+        renderer.update_elements(0, &elements);
+
+        self.apply_blend_mode();
+        self.apply_texture();
+        self.program.bind_textures();
+        self.wide_renderer.draw_elements(self.draw_command.primitive, elements.len(), 0);
+        self.program.unbind_textures();
+        self.draw_command.texture.unbind();
+        self.frame_stats.draw_calls += 1;
+        self.frame_stats.vertices += vertices.len();
+        self.frame_stats.elements += elements.len();
+    }
+
+    fn append_instance(&mut self, instance: [f32; sprite_renderer::INSTANCE_STRIDE], layer: i32) {
+        if self.is_recording() {
+            self.record(DeferredGeometry::Instance(instance), layer);
+            return;
+        }
+        if self.instances.len() + sprite_renderer::INSTANCE_STRIDE > sprite_renderer::INSTANCE_STRIDE * self.sprite_renderer.instance_size() {
+            self.frame_stats.capacity_flushes += 1;
+            self.flush_batch();
+        }
+        self.instances.extend_from_slice(&instance);
+    }
+
+    pub fn sort_mode(&self) -> SortMode {
+        self.sort_mode
+    }
+
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) {
+        if self.sort_mode != sort_mode {
+            self.flush();
+            self.sort_mode = sort_mode;
+        }
+    }
+
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    /// The layer of following draws in sorted modes, lower layers are drawn first.
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    fn is_recording(&self) -> bool {
+        self.sort_mode != SortMode::Immediate && !self.replaying
+    }
+
+    fn record(&mut self, geometry: DeferredGeometry, layer: i32) {
+        self.recording.record(&self.program, DeferredDraw {
+            layer,
+            program: self.program.clone(),
+            command: self.draw_command.clone(),
+            geometry,
+        });
+    }
+
+    fn submit_deferred_draws(&mut self) {
+        if self.recording.is_empty() {
+            return;
+        }
+        let sort_mode = self.sort_mode;
+        let deferred_draws = self.recording.take_sorted(|deferred_draw| deferred_draw.sort_key(sort_mode));
+        let program = self.program.clone();
+        self.replaying = true;
+        for deferred_draw in deferred_draws {
+            self.switch_program(deferred_draw.program);
+            self.switch_draw_command(deferred_draw.command);
+            match deferred_draw.geometry {
+                DeferredGeometry::Vertices(vertices, elements) => self.append_vertices_and_elements(vertices, Some(elements.as_slice()), deferred_draw.layer),
+                DeferredGeometry::Instance(instance) => self.append_instance(instance, deferred_draw.layer),
+                DeferredGeometry::VertexData(vertex_data, elements) => self.submit_vertex_data(&vertex_data, &elements),
+                DeferredGeometry::StaticMesh(buffers, matrix) => self.submit_static_mesh(&buffers, matrix),
+            }
+        }
+        self.flush_batch();
+        self.switch_program(program);
+        self.replaying = false;
+    }
+
+    fn switch_program(&mut self, program: Rc<opengl::Program>) {
+        if !Rc::ptr_eq(&self.program, &program) {
+            self.flush_batch();
+            self.frame_stats.program_switches += 1;
+            self.program = program;
+            self.rebind_program();
+        }
     }
 
     pub fn draw_mesh<'a>(&mut self, texture: impl Into<TextureRef<'a>>, params: impl Into<Option<MeshDrawParams>>, transform: impl Into<Option<Transform>>) {
         let texture = texture.into();
         let params = params.into().unwrap_or_default();
         let transform = transform.into().unwrap_or_default();
+        let layer = params.layer.unwrap_or(self.layer);
 
         self.switch_draw_command(DrawCommand {
             texture: texture.texture().unwrap_or_else(|| self.default_texture.clone()),
             primitive: params.primitive.unwrap_or(PrimitiveType::Triangles),
+            blend_mode: self.blend_mode,
+            instanced: false,
         });
 
         let matrix = self.transform_matrix * transform.0;
 
+        if let Some(vertex_data) = params.vertex_data {
+            self.draw_vertex_data(vertex_data, params.elements.map(Elements::into_u32), matrix, layer);
+            return;
+        }
+
         let vertices = params.vertices.map(|mut vertices| {
             for vertex in &mut vertices {
                 vertex.position = {
@@ -367,18 +798,126 @@ impl Graphics {
             }
             vertices
         }).unwrap_or_else(|| Vec::new());
-        let elements = params.elements;
-        self.append_vertices_and_elements(vertices, elements);
+        match params.elements {
+            Some(Elements::U16(elements)) => self.append_vertices_and_elements(vertices, Some(elements.as_slice()), layer),
+            Some(Elements::U32(elements)) => self.append_vertices_and_elements(vertices, Some(elements.as_slice()), layer),
+            None => self.append_vertices_and_elements::<u16>(vertices, None, layer),
+        }
+    }
+
+    fn draw_vertex_data(&mut self, mut vertex_data: VertexData, elements: Option<Vec<u32>>, matrix: Mat4, layer: i32) {
+        let vertex_count = vertex_data.len();
+        assert_eq!(vertex_data.data().len(), vertex_count * vertex_data.format().stride(), "vertex data must be whole vertices");
+        if vertex_count == 0 {
+            return;
+        }
+        let elements = elements.unwrap_or_else(|| (0..vertex_count as u32).collect());
+        assert!(elements.iter().all(|element| (*element as usize) < vertex_count), "element must < vertex count");
+
+        let format = vertex_data.format().clone();
+        if format.has_position() {
+            let stride = format.stride();
+            for vertex in vertex_data.data_mut().chunks_exact_mut(stride) {
+                let position = matrix * Vec4::new(vertex[0], vertex[1], 0.0, 1.0);
+                vertex[0] = position.x;
+                vertex[1] = position.y;
+            }
+        }
+
+        if self.is_recording() {
+            self.record(DeferredGeometry::VertexData(vertex_data, elements), layer);
+            return;
+        }
+        self.submit_vertex_data(&vertex_data, &elements);
+    }
+
+    /// Custom formats do not share the batch, each mesh is drawn immediately by a renderer for its format.
+    fn submit_vertex_data(&mut self, vertex_data: &VertexData, elements: &[u32]) {
+        let vertex_count = vertex_data.len();
+        let format = vertex_data.format();
+        self.flush_batch();
+
+        if !self.format_renderers.contains_key(format) {
+            // Without a renderer for the format there is nothing to draw with, so the mesh is skipped.
+            let renderer = RendererBuilder::new(self.gl.clone()).and_then(|builder| builder
+                .vertex_format(format.clone())
+                .init_vertex_size(BufferUsage::Stream, vertex_count)
+                .init_element_size(BufferUsage::Stream, elements.len())
+                .build());
+            let Ok(renderer) = renderer else {
+                return;
+            };
+            self.format_renderers.insert(format.clone(), renderer);
+        }
+        let renderer = self.format_renderers.get_mut(format).unwrap();
+        if renderer.vertex_size() < vertex_count {
+            renderer.init_vertex_size(BufferUsage::Stream, vertex_count);
+        }
+        if renderer.element_size() < elements.len() {
+            renderer.init_element_size(BufferUsage::Stream, elements.len());
+        }
+        renderer.update_vertex_data(0, vertex_data.data());
+        renderer.update_elements(0, elements);
+
+        self.apply_blend_mode();
+        self.apply_texture();
+        self.program.bind_textures();
+        self.format_renderers[format].draw_elements(self.draw_command.primitive, elements.len(), 0);
+        self.program.unbind_textures();
+        self.draw_command.texture.unbind();
+        self.frame_stats.draw_calls += 1;
+        self.frame_stats.vertices += vertex_count;
+        self.frame_stats.elements += elements.len();
+    }
+
+    pub fn draw_static_mesh<'a>(&mut self, texture: impl Into<TextureRef<'a>>, mesh: &Mesh, params: impl Into<Option<StaticMeshDrawParams>>, transform: impl Into<Option<Transform>>) {
+        let texture = texture.into();
+        let params = params.into().unwrap_or_default();
+        let transform = transform.into().unwrap_or_default();
+        let layer = params.layer.unwrap_or(self.layer);
+
+        self.switch_draw_command(DrawCommand {
+            texture: texture.texture().unwrap_or_else(|| self.default_texture.clone()),
+            primitive: mesh.primitive(),
+            blend_mode: self.blend_mode,
+            instanced: false,
+        });
+
+        // The mesh stays in its static buffers, so the transform goes into the projection for this draw.
+        let matrix = self.projection_matrix * self.transform_matrix * transform.0;
+        if self.is_recording() {
+            self.record(DeferredGeometry::StaticMesh(mesh.buffers().clone(), matrix), layer);
+            return;
+        }
+        self.submit_static_mesh(mesh.buffers(), matrix);
+    }
+
+    fn submit_static_mesh(&mut self, buffers: &MeshBuffers, matrix: Mat4) {
+        self.flush_batch();
+        self.program.set_uniform_matrix_4("u_projection", &matrix.to_cols_array());
+        self.apply_blend_mode();
+        self.apply_texture();
+        self.program.bind_textures();
+        buffers.draw(self.draw_command.primitive);
+        self.program.unbind_textures();
+        self.draw_command.texture.unbind();
+        self.frame_stats.draw_calls += 1;
+        self.frame_stats.vertices += buffers.vertex_count();
+        self.frame_stats.elements += buffers.element_count();
+        self.program.set_uniform_matrix_4("u_projection", &self.projection_matrix.to_cols_array());
     }
 
     pub fn draw_sprite<'a>(&mut self, texture: impl Into<TextureRef<'a>>, params: impl Into<Option<SpriteDrawParams>>, transform: impl Into<Option<Transform>>) {
         let texture = texture.into();
         let params = params.into().unwrap_or_default();
         let transform = transform.into().unwrap_or_default();
+        let layer = params.layer.unwrap_or(self.layer);
 
         self.switch_draw_command(DrawCommand {
             texture: texture.texture().unwrap_or_else(|| self.default_texture.clone()),
             primitive: PrimitiveType::Triangles,
+            blend_mode: self.blend_mode,
+            instanced: self.is_sprite_instancing(),
         });
 
         let texture_size = {
@@ -400,6 +939,20 @@ impl Graphics {
         );
         let colors = params.colors.unwrap_or_else(|| [Color::WHITE, Color::WHITE, Color::WHITE, Color::WHITE]);
 
+        if self.draw_command.instanced {
+            self.append_instance([
+                matrix.x_axis.x, matrix.x_axis.y, matrix.y_axis.x, matrix.y_axis.y,
+                matrix.w_axis.x, matrix.w_axis.y,
+                -origin.x, -origin.y, region.width, region.height,
+                uv.x, uv.y, uv.width, uv.height,
+                colors[0].red, colors[0].green, colors[0].blue, colors[0].alpha,
+                colors[1].red, colors[1].green, colors[1].blue, colors[1].alpha,
+                colors[2].red, colors[2].green, colors[2].blue, colors[2].alpha,
+                colors[3].red, colors[3].green, colors[3].blue, colors[3].alpha,
+            ], layer);
+            return;
+        }
+
         let vertices = vec![
             Vertex {
                 position: Position::new(x0y0.x, x0y0.y),
@@ -422,18 +975,21 @@ impl Graphics {
                 color: colors[3],
             },
         ];
-        let elements = SPRITE_ELEMENTS.to_vec();
-        self.append_vertices_and_elements(vertices, Some(elements));
+        self.append_vertices_and_elements(vertices, Some(SPRITE_ELEMENTS.as_slice()), layer);
     }
 
     pub fn draw_text(&mut self, font: &Font, text: &str, params: impl Into<Option<TextDrawParams>>, transform: impl Into<Option<Transform>>) {
         let params = params.into().unwrap_or_default();
         let transform = transform.into().unwrap_or_default();
+        let layer = params.layer.unwrap_or(self.layer);
 
-        self.switch_draw_command(DrawCommand {
+        let draw_command = DrawCommand {
             texture: font.cache_texture(),
             primitive: PrimitiveType::Triangles,
-        });
+            blend_mode: self.blend_mode,
+            instanced: false,
+        };
+        self.switch_draw_command(draw_command.clone());
 
         let text_size = params.text_size.unwrap_or(16.0);
         let line_metrics = font.line_metrics(text_size);
@@ -551,8 +1107,7 @@ impl Graphics {
                                     color,
                                 },
                             ];
-                            let elements = SPRITE_ELEMENTS.to_vec();
-                            self.append_vertices_and_elements(vertices, Some(elements));
+                            self.append_vertices_and_elements(vertices, Some(SPRITE_ELEMENTS.as_slice()), layer);
                             break;
                         }
                         Err(cache_error) => {
@@ -568,6 +1123,8 @@ impl Graphics {
                                     font.resize_cache((font.cache_texture_size() * 2).min(self.max_texture_size));
                                 }
                             }
+                            // Recorded draws may have replaced the current command while flushing.
+                            self.switch_draw_command(draw_command.clone());
                         }
                     }
                 }
@@ -582,6 +1139,8 @@ pub struct GraphicsConfig {
     default_wrap: Wrap,
     renderer_vertex_size: usize,
     renderer_element_size: usize,
+    renderer_instance_size: usize,
+    sprite_instancing: bool,
 }
 
 impl GraphicsConfig {
@@ -591,6 +1150,8 @@ impl GraphicsConfig {
             default_wrap: Wrap::default(),
             renderer_vertex_size: SPRITE_VERTEX_COUNT * 2048,
             renderer_element_size: SPRITE_ELEMENT_COUNT * 2048,
+            renderer_instance_size: 2048,
+            sprite_instancing: false,
         }
     }
 
@@ -614,9 +1175,20 @@ impl GraphicsConfig {
         self
     }
 
+    pub fn renderer_instance_size(mut self, size: usize) -> Self {
+        self.renderer_instance_size = size;
+        self
+    }
+
     pub fn renderer_sprite_size(mut self, size: usize) -> Self {
         self.renderer_vertex_size = SPRITE_VERTEX_COUNT * size;
         self.renderer_element_size = SPRITE_ELEMENT_COUNT * size;
+        self.renderer_instance_size = size;
+        self
+    }
+
+    pub fn sprite_instancing(mut self, sprite_instancing: bool) -> Self {
+        self.sprite_instancing = sprite_instancing;
         self
     }
 }
@@ -1,5 +1,5 @@
 use super::{Graphics, opengl, Filter, Wrap, Texture};
-use super::opengl::{Attachment, Framebuffer};
+use super::opengl::{Attachment, Framebuffer, Renderbuffer};
 use crate::error::{GameError, GameResult};
 use crate::math::Size;
 use std::rc::Rc;
@@ -7,6 +7,7 @@ use std::rc::Rc;
 pub struct Canvas {
     framebuffer: Rc<Framebuffer>,
     texture: Texture,
+    depth_stencil: Renderbuffer,
 }
 
 impl Canvas {
@@ -14,13 +15,21 @@ impl Canvas {
         let framebuffer = Framebuffer::new(graphics.gl())
             .map_err(|error| GameError::InitError(error.into()))?;
         let texture = Texture::new(graphics, size, None)?;
+        let size = texture.size();
+        let depth_stencil = Renderbuffer::new(graphics.gl())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        depth_stencil.bind();
+        depth_stencil.init_depth_stencil_storage(size.width, size.height);
+        depth_stencil.unbind();
         framebuffer.bind();
         framebuffer.attach_texture(Attachment::Color(0), Some(texture.texture().id()));
+        framebuffer.attach_renderbuffer(Attachment::DepthStencil, Some(depth_stencil.id()));
         framebuffer.check_status().map_err(|error| GameError::InitError(error.into()))?;
         framebuffer.unbind();
         Ok(Self {
             framebuffer: Rc::new(framebuffer),
             texture,
+            depth_stencil,
         })
     }
 
@@ -53,6 +62,10 @@ impl Canvas {
     }
 
     pub fn resize(&mut self, size: impl Into<Size<u32>>) {
+        let size = size.into();
         self.texture.resize(size);
+        self.depth_stencil.bind();
+        self.depth_stencil.init_depth_stencil_storage(size.width, size.height);
+        self.depth_stencil.unbind();
     }
 }
@@ -0,0 +1,82 @@
+/// Mesh indices, `U32` lifts the 65536 vertex limit of `U16`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Elements {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Elements {
+    /// Picks `U16` when every vertex can be indexed by it.
+    pub fn sequence(vertex_count: usize) -> Self {
+        if vertex_count <= u16::MAX as usize + 1 {
+            Self::U16((0..vertex_count).map(|element| element as u16).collect())
+        } else {
+            Self::U32((0..vertex_count).map(|element| element as u32).collect())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::U16(elements) => elements.len(),
+            Self::U32(elements) => elements.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn max(&self) -> Option<u32> {
+        match self {
+            Self::U16(elements) => elements.iter().max().map(|element| *element as u32),
+            Self::U32(elements) => elements.iter().max().copied(),
+        }
+    }
+
+    pub(crate) fn into_u32(self) -> Vec<u32> {
+        match self {
+            Self::U16(elements) => elements.into_iter().map(u32::from).collect(),
+            Self::U32(elements) => elements,
+        }
+    }
+}
+
+impl From<Vec<u16>> for Elements {
+    fn from(elements: Vec<u16>) -> Self {
+        Self::U16(elements)
+    }
+}
+
+impl From<&[u16]> for Elements {
+    fn from(elements: &[u16]) -> Self {
+        Self::U16(elements.to_vec())
+    }
+}
+
+impl From<Vec<u32>> for Elements {
+    fn from(elements: Vec<u32>) -> Self {
+        Self::U32(elements)
+    }
+}
+
+impl From<&[u32]> for Elements {
+    fn from(elements: &[u32]) -> Self {
+        Self::U32(elements.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Elements;
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(Elements::sequence(3), Elements::U16(vec![0, 1, 2]));
+        assert!(matches!(Elements::sequence(65536), Elements::U16(_)));
+        let elements = Elements::sequence(65537);
+        assert!(matches!(elements, Elements::U32(_)));
+        assert_eq!(elements.len(), 65537);
+        assert_eq!(elements.max(), Some(65536));
+        assert_eq!(Elements::U16(vec![2, 0, 1]).into_u32(), vec![2, 0, 1]);
+    }
+}
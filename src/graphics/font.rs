@@ -2,10 +2,11 @@ use super::{Graphics, opengl, Filter, Texture};
 use crate::error::{GameError, GameResult};
 use crate::math::{Size, Region};
 use crate::engine::Engine;
+use crate::filesystem::Reload;
 use fontdue::{FontSettings, Metrics};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -65,16 +66,21 @@ struct Cache {
     rows: Vec<Size<u32>>,
 }
 
+impl Cache {
+    fn clear(&mut self) {
+        self.draw_infos.clear();
+        self.rows.clear();
+    }
+}
+
 pub struct Font {
-    font: fontdue::Font,
-    cache: RefCell<Cache>,
+    font: Rc<RefCell<fontdue::Font>>,
+    cache: Rc<RefCell<Cache>>,
     hidpi_scale_factor: Option<f32>,
 }
 
 impl Font {
-    pub(crate) fn new(graphics: &mut Graphics, bytes: &[u8], cache_texture_size: u32) -> GameResult<Self> {
-        let font = fontdue::Font::from_bytes(bytes, FontSettings::default())
-            .map_err(|error| GameError::InitError(error.into()))?;
+    pub(crate) fn new(graphics: &mut Graphics, font: fontdue::Font, cache_texture_size: u32) -> GameResult<Self> {
         let cache_texture = Texture::for_font_cache(graphics, cache_texture_size)?;
         let cache = Cache {
             texture: cache_texture,
@@ -83,23 +89,38 @@ impl Font {
             rows: Vec::new(),
         };
         Ok(Self {
-            font,
-            cache: RefCell::new(cache),
+            font: Rc::new(RefCell::new(font)),
+            cache: Rc::new(RefCell::new(cache)),
             hidpi_scale_factor: None,
         })
     }
 
+    pub(crate) fn from_font(graphics: &mut Graphics, font: fontdue::Font) -> GameResult<Self> {
+        Self::new(graphics, font, 1024)
+    }
+
     pub fn from_bytes(graphics: &mut Graphics, bytes: &[u8]) -> GameResult<Self> {
-        Self::new(graphics, bytes, 1024)
+        Self::from_font(graphics, parse_font(bytes)?)
     }
 
     pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
         let bytes = engine.filesystem().read(path)?;
-        Self::from_bytes(engine.graphics(), &bytes)
+        let font = Self::from_bytes(engine.graphics(), &bytes)?;
+        font.watch(engine, path);
+        Ok(font)
+    }
+
+    pub(crate) fn watch(&self, engine: &mut Engine, path: &Path) {
+        engine.watch_asset(vec![path.to_owned()], FontReload {
+            font: Rc::downgrade(&self.font),
+            cache: Rc::downgrade(&self.cache),
+            path: path.to_owned(),
+        });
     }
 
     pub(crate) fn line_metrics(&self, px: f32) -> LineMetrics {
-        let horizontal_line_metrics = self.font.horizontal_line_metrics(px)
+        let horizontal_line_metrics = self.font.borrow().horizontal_line_metrics(px)
             .expect("no horizontal line metrics");
         LineMetrics {
             ascent: horizontal_line_metrics.ascent,
@@ -110,7 +131,7 @@ impl Font {
     }
 
     pub(crate) fn glyph_metrics(&self, c: char, px: f32) -> GlyphMetrics {
-        let metrics = self.font.metrics(c, px);
+        let metrics = self.font.borrow().metrics(c, px);
         GlyphMetrics {
             advance_width: metrics.advance_width,
         }
@@ -132,7 +153,7 @@ impl Font {
             let draw_info = GlyphDrawInfo::new(*metrics, hidpi_scale_factor, *uv);
             return Ok(CachedBy::Existed(draw_info));
         }
-        let metrics = self.font.metrics(c, px);
+        let metrics = self.font.borrow().metrics(c, px);
         let glyph_size = Size::new(metrics.width as u32, metrics.height as u32);
         let glyph_cache_size = Size::new(glyph_size.width + 1, glyph_size.height + 1);
         let cache_texture_size = cache.texture_size;
@@ -156,7 +177,7 @@ impl Font {
             }
         }
         if let Some(region) = region {
-            let (_, bitmap) = self.font.rasterize(c, px);
+            let (_, bitmap) = self.font.borrow().rasterize(c, px);
             let mut pixels = Vec::with_capacity(bitmap.len() * 4);
             for alpha in bitmap {
                 pixels.push(255);
@@ -187,15 +208,12 @@ impl Font {
     }
 
     pub(crate) fn clear_cache(&self) {
-        let mut cache = self.cache.borrow_mut();
-        cache.draw_infos.clear();
-        cache.rows.clear();
+        self.cache.borrow_mut().clear();
     }
 
     pub(crate) fn resize_cache(&self, cache_texture_size: u32) {
         let mut cache = self.cache.borrow_mut();
-        cache.draw_infos.clear();
-        cache.rows.clear();
+        cache.clear();
         cache.texture.resize((cache_texture_size, cache_texture_size));
         cache.texture_size = cache_texture_size;
     }
@@ -216,3 +234,30 @@ impl Font {
         self.hidpi_scale_factor = hidpi_scale_factor;
     }
 }
+
+pub(crate) fn parse_font(bytes: &[u8]) -> GameResult<fontdue::Font> {
+    fontdue::Font::from_bytes(bytes, FontSettings::default())
+        .map_err(|error| GameError::InitError(error.into()))
+}
+
+struct FontReload {
+    font: Weak<RefCell<fontdue::Font>>,
+    cache: Weak<RefCell<Cache>>,
+    path: PathBuf,
+}
+
+impl Reload for FontReload {
+    fn is_alive(&self) -> bool {
+        self.font.strong_count() > 0
+    }
+
+    fn reload(&self, engine: &mut Engine) -> GameResult {
+        let bytes = engine.filesystem().read(&self.path)?;
+        let font = parse_font(&bytes)?;
+        if let (Some(old_font), Some(cache)) = (self.font.upgrade(), self.cache.upgrade()) {
+            *old_font.borrow_mut() = font;
+            cache.borrow_mut().clear();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum MaskMode {
+    Inside,
+    Outside,
+}
@@ -0,0 +1,137 @@
+use super::{Graphics, Vertex, VertexFormat, VertexData, Elements, PrimitiveType};
+use super::opengl::{BufferUsage, ElementIndex};
+use super::renderer::{Renderer, RendererBuilder};
+use crate::error::{GameError, GameResult};
+use std::rc::Rc;
+
+enum MeshRenderer {
+    U16(Renderer<u16>),
+    U32(Renderer<u32>),
+}
+
+/// The static buffers of a mesh, shared so draws recorded in sorted modes outlive the `Mesh`.
+#[derive(Clone)]
+pub(crate) struct MeshBuffers {
+    renderer: Rc<MeshRenderer>,
+    vertex_count: usize,
+    element_count: usize,
+}
+
+impl MeshBuffers {
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    pub fn element_count(&self) -> usize {
+        self.element_count
+    }
+
+    pub fn draw(&self, primitive: PrimitiveType) {
+        match self.renderer.as_ref() {
+            MeshRenderer::U16(renderer) => renderer.draw_elements(primitive, self.element_count, 0),
+            MeshRenderer::U32(renderer) => renderer.draw_elements(primitive, self.element_count, 0),
+        }
+    }
+}
+
+/// Geometry uploaded once into static buffers, for drawing many times with `Graphics::draw_static_mesh`.
+pub struct Mesh {
+    buffers: MeshBuffers,
+    format: VertexFormat,
+    primitive: PrimitiveType,
+}
+
+impl Mesh {
+    /// Without `elements`, vertices are drawn in order with the smallest element type that fits.
+    pub fn new(graphics: &mut Graphics, primitive: PrimitiveType, vertices: &[Vertex], elements: Option<Elements>) -> GameResult<Self> {
+        let mut vertex_data = VertexData::new(VertexFormat::standard());
+        for vertex in vertices {
+            vertex_data.push_vertex(*vertex);
+        }
+        Self::with_vertex_data(graphics, primitive, &vertex_data, elements)
+    }
+
+    /// The mesh is drawn with the transform applied on the GPU, so any position attribute is left untouched.
+    pub fn with_vertex_data(graphics: &mut Graphics, primitive: PrimitiveType, vertex_data: &VertexData, elements: Option<Elements>) -> GameResult<Self> {
+        if vertex_data.data().len() != vertex_data.len() * vertex_data.format().stride() {
+            return Err(GameError::InitError("vertex data must be whole vertices".into()));
+        }
+        let elements = validate_elements(vertex_data.len(), elements)?;
+        let renderer = match &elements {
+            Elements::U16(elements) => MeshRenderer::U16(build_renderer(graphics, vertex_data, elements)?),
+            Elements::U32(elements) => MeshRenderer::U32(build_renderer(graphics, vertex_data, elements)?),
+        };
+        Ok(Self {
+            buffers: MeshBuffers {
+                renderer: Rc::new(renderer),
+                vertex_count: vertex_data.len(),
+                element_count: elements.len(),
+            },
+            format: vertex_data.format().clone(),
+            primitive,
+        })
+    }
+
+    pub fn primitive(&self) -> PrimitiveType {
+        self.primitive
+    }
+
+    pub fn set_primitive(&mut self, primitive: PrimitiveType) {
+        self.primitive = primitive;
+    }
+
+    pub fn format(&self) -> &VertexFormat {
+        &self.format
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.buffers.vertex_count
+    }
+
+    pub fn element_count(&self) -> usize {
+        self.buffers.element_count
+    }
+
+    pub(crate) fn buffers(&self) -> &MeshBuffers {
+        &self.buffers
+    }
+}
+
+fn build_renderer<E: ElementIndex>(graphics: &mut Graphics, vertex_data: &VertexData, elements: &[E]) -> GameResult<Renderer<E>> {
+    RendererBuilder::new(graphics.gl())?
+        .vertex_format(vertex_data.format().clone())
+        .init_with_vertex_data(BufferUsage::Static, vertex_data.data())
+        .init_with_elements(BufferUsage::Static, elements)
+        .build()
+}
+
+fn validate_elements(vertex_count: usize, elements: Option<Elements>) -> GameResult<Elements> {
+    if vertex_count == 0 {
+        return Err(GameError::InitError("mesh must have vertices".into()));
+    }
+    match elements {
+        Some(elements) => {
+            if let Some(element) = elements.max() && element as usize >= vertex_count {
+                return Err(GameError::InitError(format!("element out of range: {}", element).into()));
+            }
+            Ok(elements)
+        }
+        None => Ok(Elements::sequence(vertex_count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_elements;
+    use crate::graphics::Elements;
+
+    #[test]
+    fn test_validate_elements() {
+        assert_eq!(validate_elements(3, None).unwrap(), Elements::U16(vec![0, 1, 2]));
+        assert_eq!(validate_elements(4, Some(vec![0u16, 2, 1, 1, 2, 3].into())).unwrap(), Elements::U16(vec![0, 2, 1, 1, 2, 3]));
+        assert!(matches!(validate_elements(65537, None).unwrap(), Elements::U32(_)));
+        assert!(validate_elements(70000, Some(vec![0u32, 69999].into())).is_ok());
+        assert!(validate_elements(0, None).is_err());
+        assert!(validate_elements(3, Some(vec![0u16, 1, 3].into())).is_err());
+    }
+}
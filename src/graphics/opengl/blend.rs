@@ -0,0 +1,110 @@
+use glow::{Context, HasContext};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha,
+    SrcAlphaSaturate,
+}
+
+impl BlendFactor {
+    pub(crate) fn to_flag(self) -> u32 {
+        match self {
+            Self::Zero => glow::ZERO,
+            Self::One => glow::ONE,
+            Self::SrcColor => glow::SRC_COLOR,
+            Self::OneMinusSrcColor => glow::ONE_MINUS_SRC_COLOR,
+            Self::DstColor => glow::DST_COLOR,
+            Self::OneMinusDstColor => glow::ONE_MINUS_DST_COLOR,
+            Self::SrcAlpha => glow::SRC_ALPHA,
+            Self::OneMinusSrcAlpha => glow::ONE_MINUS_SRC_ALPHA,
+            Self::DstAlpha => glow::DST_ALPHA,
+            Self::OneMinusDstAlpha => glow::ONE_MINUS_DST_ALPHA,
+            Self::SrcAlphaSaturate => glow::SRC_ALPHA_SATURATE,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendEquation {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+impl BlendEquation {
+    pub(crate) fn to_flag(self) -> u32 {
+        match self {
+            Self::Add => glow::FUNC_ADD,
+            Self::Subtract => glow::FUNC_SUBTRACT,
+            Self::ReverseSubtract => glow::FUNC_REVERSE_SUBTRACT,
+            Self::Min => glow::MIN,
+            Self::Max => glow::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct BlendComponent {
+    pub equation: BlendEquation,
+    pub src: BlendFactor,
+    pub dst: BlendFactor,
+}
+
+impl BlendComponent {
+    pub fn new(equation: BlendEquation, src: BlendFactor, dst: BlendFactor) -> Self {
+        Self { equation, src, dst }
+    }
+
+    pub fn add(src: BlendFactor, dst: BlendFactor) -> Self {
+        Self::new(BlendEquation::Add, src, dst)
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendMode {
+    #[default]
+    Alpha,
+    PremultipliedAlpha,
+    Additive,
+    Multiply,
+    Screen,
+    Replace,
+    Custom {
+        color: BlendComponent,
+        alpha: BlendComponent,
+    },
+}
+
+impl BlendMode {
+    pub(crate) fn components(&self) -> (BlendComponent, BlendComponent) {
+        let component = match self {
+            Self::Alpha => BlendComponent::add(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+            Self::PremultipliedAlpha => BlendComponent::add(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+            Self::Additive => BlendComponent::add(BlendFactor::SrcAlpha, BlendFactor::One),
+            Self::Multiply => BlendComponent::add(BlendFactor::DstColor, BlendFactor::OneMinusSrcAlpha),
+            Self::Screen => BlendComponent::add(BlendFactor::One, BlendFactor::OneMinusSrcColor),
+            Self::Replace => BlendComponent::add(BlendFactor::One, BlendFactor::Zero),
+            Self::Custom { color, alpha } => return (*color, *alpha),
+        };
+        (component, component)
+    }
+
+    pub(crate) fn apply(&self, gl: &Context) {
+        let (color, alpha) = self.components();
+        unsafe {
+            gl.blend_equation_separate(color.equation.to_flag(), alpha.equation.to_flag());
+            gl.blend_func_separate(color.src.to_flag(), color.dst.to_flag(), alpha.src.to_flag(), alpha.dst.to_flag());
+        }
+    }
+}
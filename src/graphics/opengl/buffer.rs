@@ -148,11 +148,65 @@ impl VertexBuffer {
             self.gl.enable_vertex_attrib_array(index as u32);
         }
     }
+
+    pub fn set_attrib_pointer_i32(&self, index: usize, size: usize, stride: usize, offset: usize) {
+        self.set_attrib_pointer_integer(index, size, glow::INT, stride, offset);
+    }
+
+    pub fn set_attrib_pointer_u32(&self, index: usize, size: usize, stride: usize, offset: usize) {
+        self.set_attrib_pointer_integer(index, size, glow::UNSIGNED_INT, stride, offset);
+    }
+
+    pub fn set_attrib_divisor(&self, index: usize, divisor: u32) {
+        unsafe {
+            self.gl.vertex_attrib_divisor(index as u32, divisor);
+        }
+    }
+
+    fn set_attrib_pointer_integer(&self, index: usize, size: usize, data_type: u32, stride: usize, offset: usize) {
+        unsafe {
+            self.gl.vertex_attrib_pointer_i32(
+                index as u32,
+                size as i32,
+                data_type,
+                (self.unit_bytes_size * stride) as i32,
+                (self.unit_bytes_size * offset) as i32,
+            );
+            self.gl.enable_vertex_attrib_array(index as u32);
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum ElementType {
+    UnsignedShort,
+    UnsignedInt,
+}
+
+impl ElementType {
+    pub(crate) fn to_flag(self) -> u32 {
+        match self {
+            Self::UnsignedShort => glow::UNSIGNED_SHORT,
+            Self::UnsignedInt => glow::UNSIGNED_INT,
+        }
+    }
+}
+
+pub trait ElementIndex: Copy {
+    const ELEMENT_TYPE: ElementType;
+}
+
+impl ElementIndex for u16 {
+    const ELEMENT_TYPE: ElementType = ElementType::UnsignedShort;
+}
+
+impl ElementIndex for u32 {
+    const ELEMENT_TYPE: ElementType = ElementType::UnsignedInt;
 }
 
-pub type ElementBuffer = Buffer<u16>;
+pub type ElementBuffer<T = u16> = Buffer<T>;
 
-impl ElementBuffer {
+impl<T: ElementIndex> ElementBuffer<T> {
     pub fn new_element(gl: Rc<Context>) -> Result<Self, String> {
         Self::new(gl, BufferTarget::Element)
     }
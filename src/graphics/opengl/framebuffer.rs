@@ -1,4 +1,4 @@
-use super::{TextureId, Attachment};
+use super::{TextureId, RenderbufferId, Attachment};
 use glow::{Context, HasContext};
 use std::rc::Rc;
 
@@ -45,6 +45,17 @@ impl Framebuffer {
         }
     }
 
+    pub fn attach_renderbuffer(&self, attachment: Attachment, renderbuffer_id: Option<RenderbufferId>) {
+        unsafe {
+            self.gl.framebuffer_renderbuffer(
+                glow::FRAMEBUFFER,
+                attachment.to_flag(),
+                glow::RENDERBUFFER,
+                renderbuffer_id,
+            );
+        }
+    }
+
     pub fn check_status(&self) -> Result<(), String> {
         let status = unsafe {
             self.gl.check_framebuffer_status(glow::FRAMEBUFFER)
@@ -1,19 +1,25 @@
 mod program;
+mod uniform;
 mod vertex_array;
 mod buffer;
 mod primitive_type;
 mod filter;
 mod wrap;
+mod blend;
 mod texture;
 mod attachment;
 mod framebuffer;
+mod renderbuffer;
 
 pub use program::{ProgramId, Program};
+pub use uniform::{UniformLocation, UniformValue};
 pub use vertex_array::{VertexArrayId, VertexArray};
-pub use buffer::{BufferTarget, BufferUsage, BufferId, Buffer, VertexBuffer, ElementBuffer};
+pub use buffer::{BufferTarget, BufferUsage, BufferId, Buffer, VertexBuffer, ElementType, ElementIndex, ElementBuffer};
 pub use primitive_type::PrimitiveType;
 pub use filter::{FilterMode, Filter};
 pub use wrap::{WrapMode, Wrap};
+pub use blend::{BlendFactor, BlendEquation, BlendComponent, BlendMode};
 pub use texture::{TextureId, Texture};
 pub use attachment::Attachment;
 pub use framebuffer::{FramebufferId, Framebuffer};
+pub use renderbuffer::{RenderbufferId, Renderbuffer};
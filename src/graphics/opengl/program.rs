@@ -1,82 +1,275 @@
+use super::{UniformLocation, UniformValue, Texture};
 use glow::{Context, HasContext};
 use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 
 pub type ProgramId = <Context as HasContext>::Program;
 
 pub struct Program {
     gl: Rc<Context>,
-    id: ProgramId,
+    id: Cell<ProgramId>,
+    locations: RefCell<HashMap<String, Option<UniformLocation>>>,
+    uniforms: RefCell<HashMap<String, UniformValue>>,
+    dirty_uniforms: RefCell<HashSet<String>>,
+    samplers: RefCell<Samplers<Rc<Texture>>>,
 }
 
 impl Program {
     pub fn new(gl: Rc<Context>, vertex_shader_source: &str, fragment_shader_source: &str) -> Result<Self, String> {
-        let id = unsafe {
-            let vertex_shader_id = gl.create_shader(glow::VERTEX_SHADER)?;
-            gl.shader_source(vertex_shader_id, vertex_shader_source);
-            gl.compile_shader(vertex_shader_id);
-            if !gl.get_shader_compile_status(vertex_shader_id) {
-                return Err(gl.get_shader_info_log(vertex_shader_id));
-            }
+        let id = link(&gl, vertex_shader_source, fragment_shader_source)?;
+        Ok(Self {
+            gl,
+            id: Cell::new(id),
+            locations: RefCell::new(HashMap::new()),
+            uniforms: RefCell::new(HashMap::new()),
+            dirty_uniforms: RefCell::new(HashSet::new()),
+            samplers: RefCell::new(Samplers::new()),
+        })
+    }
 
-            let fragment_shader_id = gl.create_shader(glow::FRAGMENT_SHADER)?;
-            gl.shader_source(fragment_shader_id, fragment_shader_source);
-            gl.compile_shader(fragment_shader_id);
-            if !gl.get_shader_compile_status(fragment_shader_id) {
-                return Err(gl.get_shader_info_log(fragment_shader_id));
-            }
+    pub fn relink(&self, vertex_shader_source: &str, fragment_shader_source: &str) -> Result<(), String> {
+        let id = link(&self.gl, vertex_shader_source, fragment_shader_source)?;
+        let old_id = self.id.replace(id);
+        unsafe {
+            self.gl.delete_program(old_id);
+        }
+        self.locations.borrow_mut().clear();
+        self.dirty_uniforms.borrow_mut().extend(self.uniforms.borrow().keys().cloned());
+        Ok(())
+    }
 
-            let program_id = gl.create_program()?;
+    pub fn id(&self) -> ProgramId {
+        self.id.get()
+    }
 
-            gl.attach_shader(program_id, vertex_shader_id);
-            gl.attach_shader(program_id, fragment_shader_id);
+    pub fn bind(&self) {
+        unsafe {
+            self.gl.use_program(Some(self.id()));
+        }
+    }
 
-            gl.link_program(program_id);
-            if !gl.get_program_link_status(program_id) {
-                return Err(gl.get_program_info_log(program_id));
-            }
+    pub fn unbind(&self) {
+        unsafe {
+            self.gl.use_program(None);
+        }
+    }
 
-            gl.delete_shader(vertex_shader_id);
-            gl.delete_shader(fragment_shader_id);
+    pub fn uniform_location(&self, name: &str) -> Option<UniformLocation> {
+        *self.locations.borrow_mut()
+            .entry(name.to_owned())
+            .or_insert_with(|| unsafe { self.gl.get_uniform_location(self.id(), name) })
+    }
 
-            program_id
+    pub fn set_uniform(&self, name: &str, value: UniformValue) -> Result<(), String> {
+        if self.uniform_location(name).is_none() {
+            return Err(format!("uniform `{}` not found", name));
+        }
+        self.uniforms.borrow_mut().insert(name.to_owned(), value);
+        self.dirty_uniforms.borrow_mut().insert(name.to_owned());
+        Ok(())
+    }
+
+    pub fn has_dirty_uniforms(&self) -> bool {
+        !self.dirty_uniforms.borrow().is_empty() || self.samplers.borrow().has_pending()
+    }
+
+    /// Uploads the uniforms and takes the sampler textures changed since the last call, returning how many sampler
+    /// textures were switched. The program must be bound.
+    pub fn apply_uniforms(&self) -> usize {
+        let uniforms = self.uniforms.borrow();
+        for name in self.dirty_uniforms.borrow_mut().drain() {
+            if let (Some(value), Some(location)) = (uniforms.get(&name), self.uniform_location(&name)) {
+                value.upload(&self.gl, &location);
+            }
+        }
+        self.samplers.borrow_mut().apply()
+    }
+
+    /// Binds `texture` to the sampler `name` from the next `apply_uniforms`. Samplers take texture units from 1, unit 0 is kept for the draw texture.
+    pub fn set_texture(&self, name: &str, texture: Option<Rc<Texture>>) -> Result<(), String> {
+        let mut samplers = self.samplers.borrow_mut();
+        let index = match samplers.position(name) {
+            Some(index) => index,
+            None => {
+                let max_unit_count = unsafe { self.gl.get_parameter_i32(glow::MAX_TEXTURE_IMAGE_UNITS) } as usize;
+                if samplers.len() + 1 >= max_unit_count {
+                    return Err(format!("no texture unit left for sampler `{}`", name));
+                }
+                samplers.len()
+            }
         };
-        Ok(Self { gl, id })
+        self.set_uniform(name, UniformValue::Int(index as i32 + 1))?;
+        samplers.set(name, texture);
+        Ok(())
     }
 
-    pub fn id(&self) -> ProgramId {
-        self.id
+    pub fn bind_textures(&self) {
+        for (index, texture) in self.samplers.borrow().textures() {
+            texture.bind_to_unit(index as u32 + 1);
+        }
     }
 
-    pub fn bind(&self) {
-        unsafe {
-            self.gl.use_program(Some(self.id));
+    pub fn unbind_textures(&self) {
+        for (index, texture) in self.samplers.borrow().textures() {
+            texture.unbind_from_unit(index as u32 + 1);
         }
     }
 
-    pub fn unbind(&self) {
+    pub fn set_uniform_1_f32(&self, name: &str, value: f32) {
         unsafe {
-            self.gl.use_program(None);
+            let location = self.gl.get_uniform_location(self.id(), name);
+            self.gl.uniform_1_f32(location.as_ref(), value);
         }
     }
 
     pub fn set_uniform_matrix_4(&self, name: &str, mat4: &[f32; 16]) {
         unsafe {
-            let location = self.gl.get_uniform_location(self.id, name);
+            let location = self.gl.get_uniform_location(self.id(), name);
             self.gl.uniform_matrix_4_f32_slice(location.as_ref(), false, mat4);
         }
     }
 }
 
+/// Sampler textures by slot. Changes wait in `pending` until `apply`, so draws batched before them keep their textures.
+struct Samplers<T> {
+    textures: Vec<(String, Option<T>)>,
+    pending: Vec<(usize, Option<T>)>,
+}
+
+impl<T: PartialEq> Samplers<T> {
+    fn new() -> Self {
+        Self {
+            textures: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.textures.iter().position(|(texture_name, _)| texture_name == name)
+    }
+
+    fn set(&mut self, name: &str, texture: Option<T>) -> usize {
+        let index = match self.position(name) {
+            Some(index) => index,
+            None => {
+                self.textures.push((name.to_owned(), None));
+                self.textures.len() - 1
+            }
+        };
+        self.pending.push((index, texture));
+        index
+    }
+
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Returns the number of slots whose texture changed.
+    fn apply(&mut self) -> usize {
+        let mut switch_count = 0;
+        for (index, texture) in self.pending.drain(..) {
+            if self.textures[index].1 != texture {
+                self.textures[index].1 = texture;
+                switch_count += 1;
+            }
+        }
+        switch_count
+    }
+
+    fn textures(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.textures.iter()
+            .enumerate()
+            .filter_map(|(index, (_, texture))| texture.as_ref().map(|texture| (index, texture)))
+    }
+}
+
+fn link(gl: &Context, vertex_shader_source: &str, fragment_shader_source: &str) -> Result<ProgramId, String> {
+    unsafe {
+        let vertex_shader_id = compile(gl, glow::VERTEX_SHADER, vertex_shader_source)?;
+        let fragment_shader_id = match compile(gl, glow::FRAGMENT_SHADER, fragment_shader_source) {
+            Ok(fragment_shader_id) => fragment_shader_id,
+            Err(error) => {
+                gl.delete_shader(vertex_shader_id);
+                return Err(error);
+            }
+        };
+
+        let program_id = gl.create_program()?;
+
+        gl.attach_shader(program_id, vertex_shader_id);
+        gl.attach_shader(program_id, fragment_shader_id);
+
+        gl.link_program(program_id);
+
+        gl.delete_shader(vertex_shader_id);
+        gl.delete_shader(fragment_shader_id);
+
+        if !gl.get_program_link_status(program_id) {
+            let error = gl.get_program_info_log(program_id);
+            gl.delete_program(program_id);
+            return Err(error);
+        }
+
+        Ok(program_id)
+    }
+}
+
+fn compile(gl: &Context, shader_type: u32, source: &str) -> Result<<Context as HasContext>::Shader, String> {
+    unsafe {
+        let shader_id = gl.create_shader(shader_type)?;
+        gl.shader_source(shader_id, source);
+        gl.compile_shader(shader_id);
+        if !gl.get_shader_compile_status(shader_id) {
+            let error = gl.get_shader_info_log(shader_id);
+            gl.delete_shader(shader_id);
+            return Err(error);
+        }
+        Ok(shader_id)
+    }
+}
+
 impl Drop for Program {
     fn drop(&mut self) {
         unsafe {
-            self.gl.delete_program(self.id);
+            self.gl.delete_program(self.id());
         }
     }
 }
 
 impl PartialEq for Program {
     fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+        self.id() == other.id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Samplers;
+
+    #[test]
+    fn test_samplers() {
+        let mut samplers = Samplers::new();
+        assert_eq!(samplers.set("u_noise", Some(1)), 0);
+        assert!(samplers.has_pending());
+        assert_eq!(samplers.textures().count(), 0);
+        assert_eq!(samplers.apply(), 1);
+        assert!(!samplers.has_pending());
+        assert_eq!(samplers.textures().collect::<Vec<_>>(), vec![(0, &1)]);
+        assert_eq!(samplers.set("u_mask", Some(2)), 1);
+        assert_eq!(samplers.set("u_noise", Some(3)), 0);
+        assert_eq!(samplers.textures().collect::<Vec<_>>(), vec![(0, &1)]);
+        assert_eq!(samplers.apply(), 2);
+        assert_eq!(samplers.textures().collect::<Vec<_>>(), vec![(0, &3), (1, &2)]);
+        samplers.set("u_mask", Some(2));
+        assert_eq!(samplers.apply(), 0);
+        samplers.set("u_noise", None);
+        assert_eq!(samplers.apply(), 1);
+        assert_eq!(samplers.len(), 2);
+        assert_eq!(samplers.textures().collect::<Vec<_>>(), vec![(1, &2)]);
     }
 }
@@ -0,0 +1,54 @@
+use glow::{Context, HasContext};
+use std::rc::Rc;
+
+pub type RenderbufferId = <Context as HasContext>::Renderbuffer;
+
+pub struct Renderbuffer {
+    gl: Rc<Context>,
+    id: RenderbufferId,
+}
+
+impl Renderbuffer {
+    pub fn new(gl: Rc<Context>) -> Result<Self, String> {
+        let id = unsafe {
+            gl.create_renderbuffer()?
+        };
+        Ok(Self { gl, id })
+    }
+
+    pub fn id(&self) -> RenderbufferId {
+        self.id
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            self.gl.bind_renderbuffer(glow::RENDERBUFFER, Some(self.id));
+        }
+    }
+
+    pub fn unbind(&self) {
+        unsafe {
+            self.gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        }
+    }
+
+    pub fn init_depth_stencil_storage(&self, width: u32, height: u32) {
+        unsafe {
+            self.gl.renderbuffer_storage(glow::RENDERBUFFER, glow::DEPTH24_STENCIL8, width as i32, height as i32);
+        }
+    }
+}
+
+impl Drop for Renderbuffer {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.delete_renderbuffer(self.id);
+        }
+    }
+}
+
+impl PartialEq for Renderbuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
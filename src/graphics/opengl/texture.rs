@@ -1,12 +1,17 @@
 use super::{Filter, Wrap};
+use crate::math::Size;
 use glow::{Context, HasContext, PixelUnpackData};
 use std::rc::Rc;
+use std::cell::Cell;
 
 pub type TextureId = <Context as HasContext>::Texture;
 
 pub struct Texture {
     gl: Rc<Context>,
     id: TextureId,
+    size: Cell<Size<u32>>,
+    mipmap_filter: Cell<bool>,
+    mipmap_generated: Cell<bool>,
 }
 
 impl Texture {
@@ -14,13 +19,31 @@ impl Texture {
         let id = unsafe {
             gl.create_texture()?
         };
-        Ok(Self { gl, id })
+        Ok(Self {
+            gl,
+            id,
+            size: Cell::new(Size::zero()),
+            mipmap_filter: Cell::new(false),
+            mipmap_generated: Cell::new(false),
+        })
     }
 
     pub fn id(&self) -> TextureId {
         self.id
     }
 
+    pub fn size(&self) -> Size<u32> {
+        self.size.get()
+    }
+
+    pub fn is_mipmap_filter(&self) -> bool {
+        self.mipmap_filter.get()
+    }
+
+    pub fn is_mipmap_generated(&self) -> bool {
+        self.mipmap_generated.get()
+    }
+
     pub fn bind(&self) {
         unsafe {
             self.gl.bind_texture(glow::TEXTURE_2D, Some(self.id));
@@ -33,6 +56,22 @@ impl Texture {
         }
     }
 
+    pub fn bind_to_unit(&self, unit: u32) {
+        unsafe {
+            self.gl.active_texture(glow::TEXTURE0 + unit);
+            self.gl.bind_texture(glow::TEXTURE_2D, Some(self.id));
+            self.gl.active_texture(glow::TEXTURE0);
+        }
+    }
+
+    pub fn unbind_from_unit(&self, unit: u32) {
+        unsafe {
+            self.gl.active_texture(glow::TEXTURE0 + unit);
+            self.gl.bind_texture(glow::TEXTURE_2D, None);
+            self.gl.active_texture(glow::TEXTURE0);
+        }
+    }
+
     pub fn init_image(&self, width: u32, height: u32, pixels: Option<&[u8]>) {
         unsafe {
             self.gl.tex_image_2d(
@@ -47,6 +86,8 @@ impl Texture {
                 PixelUnpackData::Slice(pixels),
             );
         }
+        self.size.set(Size::new(width, height));
+        self.mipmap_generated.set(false);
     }
 
     pub fn sub_image(&self, offset_x: u32, offset_y: u32, width: u32, height: u32, pixels: Option<&[u8]>) {
@@ -63,6 +104,7 @@ impl Texture {
                 PixelUnpackData::Slice(pixels),
             );
         }
+        self.mipmap_generated.set(false);
     }
 
     pub fn set_filter(&self, filter: Filter) {
@@ -70,12 +112,14 @@ impl Texture {
             self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, filter.to_min_flag() as i32);
             self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, filter.to_mag_flag() as i32);
         }
+        self.mipmap_filter.set(filter.mipmap.is_some());
     }
 
     pub fn generate_mipmap(&self) {
         unsafe {
             self.gl.generate_mipmap(glow::TEXTURE_2D);
         }
+        self.mipmap_generated.set(true);
     }
 
     pub fn set_wrap(&self, wrap: Wrap) {
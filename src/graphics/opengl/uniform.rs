@@ -0,0 +1,178 @@
+use crate::graphics::Color;
+use crate::math::{Vector, Transform};
+use glow::{Context, HasContext};
+
+pub type UniformLocation = <Context as HasContext>::UniformLocation;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UniformValue {
+    Float(f32),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Int(i32),
+    Mat3([f32; 9]),
+    Mat4([f32; 16]),
+    FloatArray(Vec<f32>),
+    Vec2Array(Vec<[f32; 2]>),
+    Vec3Array(Vec<[f32; 3]>),
+    Vec4Array(Vec<[f32; 4]>),
+    IntArray(Vec<i32>),
+}
+
+impl UniformValue {
+    pub(crate) fn upload(&self, gl: &Context, location: &UniformLocation) {
+        let location = Some(location);
+        unsafe {
+            match self {
+                Self::Float(value) => gl.uniform_1_f32(location, *value),
+                Self::Vec2(value) => gl.uniform_2_f32_slice(location, value),
+                Self::Vec3(value) => gl.uniform_3_f32_slice(location, value),
+                Self::Vec4(value) => gl.uniform_4_f32_slice(location, value),
+                Self::Int(value) => gl.uniform_1_i32(location, *value),
+                Self::Mat3(value) => gl.uniform_matrix_3_f32_slice(location, false, value),
+                Self::Mat4(value) => gl.uniform_matrix_4_f32_slice(location, false, value),
+                Self::FloatArray(values) => gl.uniform_1_f32_slice(location, values),
+                Self::Vec2Array(values) => gl.uniform_2_f32_slice(location, values.as_flattened()),
+                Self::Vec3Array(values) => gl.uniform_3_f32_slice(location, values.as_flattened()),
+                Self::Vec4Array(values) => gl.uniform_4_f32_slice(location, values.as_flattened()),
+                Self::IntArray(values) => gl.uniform_1_i32_slice(location, values),
+            }
+        }
+    }
+}
+
+impl From<f32> for UniformValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<[f32; 2]> for UniformValue {
+    fn from(value: [f32; 2]) -> Self {
+        Self::Vec2(value)
+    }
+}
+
+impl From<[f32; 3]> for UniformValue {
+    fn from(value: [f32; 3]) -> Self {
+        Self::Vec3(value)
+    }
+}
+
+impl From<[f32; 4]> for UniformValue {
+    fn from(value: [f32; 4]) -> Self {
+        Self::Vec4(value)
+    }
+}
+
+impl From<i32> for UniformValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<[f32; 9]> for UniformValue {
+    fn from(value: [f32; 9]) -> Self {
+        Self::Mat3(value)
+    }
+}
+
+impl From<[f32; 16]> for UniformValue {
+    fn from(value: [f32; 16]) -> Self {
+        Self::Mat4(value)
+    }
+}
+
+impl From<Vector> for UniformValue {
+    fn from(vector: Vector) -> Self {
+        Self::Vec2([vector.x, vector.y])
+    }
+}
+
+impl From<Color> for UniformValue {
+    fn from(color: Color) -> Self {
+        Self::Vec4([color.red, color.green, color.blue, color.alpha])
+    }
+}
+
+impl From<Transform> for UniformValue {
+    fn from(transform: Transform) -> Self {
+        Self::Mat4(transform.0.to_cols_array())
+    }
+}
+
+impl From<Vec<f32>> for UniformValue {
+    fn from(values: Vec<f32>) -> Self {
+        Self::FloatArray(values)
+    }
+}
+
+impl From<&[f32]> for UniformValue {
+    fn from(values: &[f32]) -> Self {
+        Self::FloatArray(values.to_vec())
+    }
+}
+
+impl From<Vec<[f32; 2]>> for UniformValue {
+    fn from(values: Vec<[f32; 2]>) -> Self {
+        Self::Vec2Array(values)
+    }
+}
+
+impl From<Vec<[f32; 3]>> for UniformValue {
+    fn from(values: Vec<[f32; 3]>) -> Self {
+        Self::Vec3Array(values)
+    }
+}
+
+impl From<Vec<[f32; 4]>> for UniformValue {
+    fn from(values: Vec<[f32; 4]>) -> Self {
+        Self::Vec4Array(values)
+    }
+}
+
+impl From<Vec<Vector>> for UniformValue {
+    fn from(vectors: Vec<Vector>) -> Self {
+        Self::Vec2Array(vectors.into_iter().map(|vector| [vector.x, vector.y]).collect())
+    }
+}
+
+impl From<Vec<Color>> for UniformValue {
+    fn from(colors: Vec<Color>) -> Self {
+        Self::Vec4Array(colors.into_iter().map(|color| [color.red, color.green, color.blue, color.alpha]).collect())
+    }
+}
+
+impl From<Vec<i32>> for UniformValue {
+    fn from(values: Vec<i32>) -> Self {
+        Self::IntArray(values)
+    }
+}
+
+impl From<&[i32]> for UniformValue {
+    fn from(values: &[i32]) -> Self {
+        Self::IntArray(values.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniformValue;
+    use crate::graphics::Color;
+    use crate::math::{Vector, Transform};
+
+    #[test]
+    fn test_from() {
+        assert_eq!(UniformValue::from(1.0), UniformValue::Float(1.0));
+        assert_eq!(UniformValue::from(Vector::new(1.0, 2.0)), UniformValue::Vec2([1.0, 2.0]));
+        assert_eq!(UniformValue::from(Color::new(0.1, 0.2, 0.3, 0.4)), UniformValue::Vec4([0.1, 0.2, 0.3, 0.4]));
+        assert_eq!(UniformValue::from(Transform::default()), UniformValue::Mat4([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]));
+        assert_eq!(UniformValue::from(vec![Color::WHITE]), UniformValue::Vec4Array(vec![[1.0, 1.0, 1.0, 1.0]]));
+    }
+}
@@ -1,4 +1,4 @@
-use super::PrimitiveType;
+use super::{PrimitiveType, ElementType};
 use glow::{Context, HasContext};
 use std::rc::Rc;
 
@@ -39,9 +39,15 @@ impl VertexArray {
         }
     }
 
-    pub fn draw_elements(&self, primitive: PrimitiveType, count: usize, offset: usize) {
+    pub fn draw_elements(&self, primitive: PrimitiveType, element_type: ElementType, count: usize, offset: usize) {
         unsafe {
-            self.gl.draw_elements(primitive.to_flag(), count as i32, glow::UNSIGNED_SHORT, offset as i32);
+            self.gl.draw_elements(primitive.to_flag(), count as i32, element_type.to_flag(), offset as i32);
+        }
+    }
+
+    pub fn draw_elements_instanced(&self, primitive: PrimitiveType, element_type: ElementType, count: usize, offset: usize, instance_count: usize) {
+        unsafe {
+            self.gl.draw_elements_instanced(primitive.to_flag(), count as i32, element_type.to_flag(), offset as i32, instance_count as i32);
         }
     }
 }
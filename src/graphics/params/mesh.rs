@@ -1,10 +1,12 @@
-use super::{PrimitiveType, Vertex};
+use super::{PrimitiveType, Vertex, VertexData, Elements};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct MeshDrawParams {
     pub primitive: Option<PrimitiveType>,
     pub vertices: Option<Vec<Vertex>>,
-    pub elements: Option<Vec<u16>>,
+    pub vertex_data: Option<VertexData>,
+    pub elements: Option<Elements>,
+    pub layer: Option<i32>,
 }
 
 impl MeshDrawParams {
@@ -18,8 +20,35 @@ impl MeshDrawParams {
         self
     }
 
-    pub fn elements(mut self, elements: impl Into<Option<Vec<u16>>>) -> Self {
+    /// Vertices in a custom format, used instead of `vertices` when set.
+    pub fn vertex_data(mut self, vertex_data: impl Into<Option<VertexData>>) -> Self {
+        self.vertex_data = vertex_data.into();
+        self
+    }
+
+    pub fn elements(mut self, elements: impl Into<Option<Elements>>) -> Self {
         self.elements = elements.into();
         self
     }
+
+    /// Overrides `Graphics::layer` for this draw. Only takes effect in a sorted `SortMode`,
+    /// `SortMode::Immediate` draws in call order.
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = Some(layer);
+        self
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct StaticMeshDrawParams {
+    pub layer: Option<i32>,
+}
+
+impl StaticMeshDrawParams {
+    /// Overrides `Graphics::layer` for this draw. Only takes effect in a sorted `SortMode`,
+    /// `SortMode::Immediate` draws in call order.
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = Some(layer);
+        self
+    }
 }
@@ -2,8 +2,8 @@ mod mesh;
 mod sprite;
 mod text;
 
-use super::{PrimitiveType, Color, Vertex};
+use super::{PrimitiveType, Color, Vertex, VertexData, Elements};
 
-pub use mesh::MeshDrawParams;
+pub use mesh::{MeshDrawParams, StaticMeshDrawParams};
 pub use sprite::SpriteDrawParams;
 pub use text::{TextLayoutGravity, TextDrawParams};
@@ -6,6 +6,7 @@ pub struct SpriteDrawParams {
     pub region: Option<Region>,
     pub origin: Option<Position>,
     pub colors: Option<[Color; 4]>,
+    pub layer: Option<i32>,
 }
 
 impl SpriteDrawParams {
@@ -29,4 +30,11 @@ impl SpriteDrawParams {
         self.colors = Some([color, color, color, color]);
         self
     }
+
+    /// Overrides `Graphics::layer` for this draw. Only takes effect in a sorted `SortMode`,
+    /// `SortMode::Immediate` draws in call order.
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = Some(layer);
+        self
+    }
 }
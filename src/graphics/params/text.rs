@@ -26,6 +26,7 @@ pub struct TextDrawParams {
     pub vertical_gravity: Option<TextLayoutGravity>,
     pub origin: Option<Position>,
     pub color: Option<Color>,
+    pub layer: Option<i32>,
 }
 
 impl TextDrawParams {
@@ -78,4 +79,11 @@ impl TextDrawParams {
         self.color = Some(color.into());
         self
     }
+
+    /// Overrides `Graphics::layer` for this draw. Only takes effect in a sorted `SortMode`,
+    /// `SortMode::Immediate` draws in call order.
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = Some(layer);
+        self
+    }
 }
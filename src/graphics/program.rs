@@ -1,12 +1,14 @@
-use super::{Graphics, opengl};
+use super::{Graphics, UniformValue, TextureRef, opengl};
 use crate::error::{GameError, GameResult};
 use crate::engine::Engine;
+use crate::filesystem::Reload;
 use glow::Context;
-use std::rc::Rc;
-use std::path::Path;
+use std::rc::{Rc, Weak};
+use std::path::{Path, PathBuf};
 
 const DEFAULT_VERTEX_SHADER_SOURCE: &str = include_str!("shaders/default.vert");
 const DEFAULT_FRAGMENT_SHADER_SOURCE: &str = include_str!("shaders/default.frag");
+const INSTANCED_VERTEX_SHADER_SOURCE: &str = include_str!("shaders/instanced.vert");
 
 pub struct Program {
     program: Rc<opengl::Program>,
@@ -23,9 +25,31 @@ impl Program {
     }
 
     pub fn load(engine: &mut Engine, vertex_shader_path: impl AsRef<Path>, fragment_shader_path: impl AsRef<Path>) -> GameResult<Self> {
+        let vertex_shader_path = vertex_shader_path.as_ref();
+        let fragment_shader_path = fragment_shader_path.as_ref();
         let vertex_shader_source = engine.filesystem().read_to_string(vertex_shader_path)?;
         let fragment_shader_source = engine.filesystem().read_to_string(fragment_shader_path)?;
-        Self::new(engine.graphics(), &vertex_shader_source, &fragment_shader_source)
+        let program = Self::new(engine.graphics(), &vertex_shader_source, &fragment_shader_source)?;
+        program.watch(engine, vertex_shader_path, fragment_shader_path);
+        Ok(program)
+    }
+
+    pub(crate) fn watch(&self, engine: &mut Engine, vertex_shader_path: &Path, fragment_shader_path: &Path) {
+        engine.watch_asset(vec![vertex_shader_path.to_owned(), fragment_shader_path.to_owned()], ProgramReload {
+            program: Rc::downgrade(&self.program),
+            vertex_shader_path: vertex_shader_path.to_owned(),
+            fragment_shader_path: fragment_shader_path.to_owned(),
+        });
+    }
+
+    pub fn set_uniform(&self, name: &str, value: impl Into<UniformValue>) -> GameResult {
+        self.program.set_uniform(name, value.into())
+            .map_err(|error| GameError::RuntimeError(error.into()))
+    }
+
+    pub fn set_texture<'a>(&self, name: &str, texture: impl Into<TextureRef<'a>>) -> GameResult {
+        self.program.set_texture(name, texture.into().texture())
+            .map_err(|error| GameError::RuntimeError(error.into()))
     }
 
     pub(crate) fn default(gl: Rc<Context>) -> GameResult<Rc<opengl::Program>> {
@@ -37,7 +61,38 @@ impl Program {
         Ok(Rc::new(program))
     }
 
+    pub(crate) fn instanced(gl: Rc<Context>) -> GameResult<Rc<opengl::Program>> {
+        let program = opengl::Program::new(
+            gl,
+            INSTANCED_VERTEX_SHADER_SOURCE,
+            DEFAULT_FRAGMENT_SHADER_SOURCE,
+        ).map_err(|error| GameError::InitError(error.into()))?;
+        Ok(Rc::new(program))
+    }
+
     pub(crate) fn program(&self) -> &Rc<opengl::Program> {
         &self.program
     }
 }
+
+struct ProgramReload {
+    program: Weak<opengl::Program>,
+    vertex_shader_path: PathBuf,
+    fragment_shader_path: PathBuf,
+}
+
+impl Reload for ProgramReload {
+    fn is_alive(&self) -> bool {
+        self.program.strong_count() > 0
+    }
+
+    fn reload(&self, engine: &mut Engine) -> GameResult {
+        let vertex_shader_source = engine.filesystem().read_to_string(&self.vertex_shader_path)?;
+        let fragment_shader_source = engine.filesystem().read_to_string(&self.fragment_shader_path)?;
+        if let Some(program) = self.program.upgrade() {
+            program.relink(&vertex_shader_source, &fragment_shader_source)
+                .map_err(|error| GameError::InitError(error.into()))?;
+        }
+        Ok(())
+    }
+}
@@ -1,22 +1,23 @@
-use super::{vertex, Vertex};
-use super::opengl::{VertexArray, BufferUsage, Buffer, VertexBuffer, ElementBuffer, PrimitiveType};
+use super::{Vertex, VertexFormat, AttributeType};
+use super::opengl::{VertexArray, BufferUsage, Buffer, VertexBuffer, ElementIndex, ElementBuffer, PrimitiveType};
 use crate::error::{GameError, GameResult};
 use glow::Context;
 use std::rc::Rc;
 
-pub struct Renderer {
+pub struct Renderer<E: ElementIndex = u16> {
+    format: VertexFormat,
     vertex_array: VertexArray,
     vertex_buffer: VertexBuffer,
     vertex_size: usize,
-    element_buffer: ElementBuffer,
+    element_buffer: ElementBuffer<E>,
     element_size: usize,
 }
 
-impl Renderer {
+impl<E: ElementIndex> Renderer<E> {
     pub fn init_vertex_size(&mut self, usage: BufferUsage, size: usize) {
         self.vertex_buffer.bind();
-        self.vertex_buffer.init_size(usage, vertex::ATTRIBUTE_STRIDE * size);
-        init_vertex_attribute_pointer(&self.vertex_buffer);
+        self.vertex_buffer.init_size(usage, self.format.stride() * size);
+        init_vertex_attribute_pointer(&self.format, &self.vertex_buffer);
         self.vertex_buffer.unbind();
         self.vertex_size = size;
     }
@@ -24,7 +25,7 @@ impl Renderer {
     pub fn init_with_vertices(&mut self, usage: BufferUsage, vertices: &[Vertex]) {
         self.vertex_buffer.bind();
         self.vertex_buffer.init_with_data(usage, &convert_vertices_to_data(vertices));
-        init_vertex_attribute_pointer(&self.vertex_buffer);
+        init_vertex_attribute_pointer(&self.format, &self.vertex_buffer);
         self.vertex_buffer.unbind();
         self.vertex_size = vertices.len();
     }
@@ -35,6 +36,16 @@ impl Renderer {
         self.vertex_buffer.unbind();
     }
 
+    pub fn update_vertex_data(&self, offset: usize, data: &[f32]) {
+        self.vertex_buffer.bind();
+        self.vertex_buffer.sub_data(offset, data);
+        self.vertex_buffer.unbind();
+    }
+
+    pub fn format(&self) -> &VertexFormat {
+        &self.format
+    }
+
     pub fn vertex_size(&self) -> usize {
         self.vertex_size
     }
@@ -46,14 +57,14 @@ impl Renderer {
         self.element_size = size;
     }
 
-    pub fn init_with_elements(&mut self, usage: BufferUsage, elements: &[u16]) {
+    pub fn init_with_elements(&mut self, usage: BufferUsage, elements: &[E]) {
         self.element_buffer.bind();
         self.element_buffer.init_with_data(usage, elements);
         self.element_buffer.unbind();
         self.element_size = elements.len();
     }
 
-    pub fn update_elements(&self, offset: usize, elements: &[u16]) {
+    pub fn update_elements(&self, offset: usize, elements: &[E]) {
         self.element_buffer.bind();
         self.element_buffer.sub_data(offset, elements);
         self.element_buffer.unbind();
@@ -65,27 +76,29 @@ impl Renderer {
 
     pub fn draw_elements(&self, primitive: PrimitiveType, count: usize, offset: usize) {
         self.vertex_array.bind();
-        self.vertex_array.draw_elements(primitive, count, offset);
+        self.vertex_array.draw_elements(primitive, E::ELEMENT_TYPE, count, offset);
         self.vertex_array.unbind();
     }
 }
 
-pub struct RendererBuilder {
+pub struct RendererBuilder<E: ElementIndex = u16> {
     gl: Rc<Context>,
+    format: VertexFormat,
     vertex_array: VertexArray,
     vertex_buffer: Option<VertexBuffer>,
     vertex_size: Option<usize>,
-    element_buffer: Option<ElementBuffer>,
+    element_buffer: Option<ElementBuffer<E>>,
     element_size: Option<usize>,
 }
 
-impl RendererBuilder {
+impl<E: ElementIndex> RendererBuilder<E> {
     pub fn new(gl: Rc<Context>) -> GameResult<Self> {
         let vertex_array = VertexArray::new(gl.clone())
             .map_err(|error| GameError::InitError(error.into()))?;
         vertex_array.bind();
         Ok(Self {
             gl,
+            format: VertexFormat::standard(),
             vertex_array,
             vertex_buffer: None,
             vertex_size: None,
@@ -98,12 +111,18 @@ impl RendererBuilder {
         assert!(self.vertex_buffer.is_none(), "vertex buffer has been setup");
     }
 
+    pub fn vertex_format(mut self, format: VertexFormat) -> Self {
+        self.assert_vertex_buffer_not_init();
+        self.format = format;
+        self
+    }
+
     pub fn init_vertex_size(mut self, usage: BufferUsage, size: usize) -> Self {
         self.assert_vertex_buffer_not_init();
         let vertex_buffer = Buffer::new_vertex(self.gl.clone()).unwrap();
         vertex_buffer.bind();
-        vertex_buffer.init_size(usage, vertex::ATTRIBUTE_STRIDE * size);
-        init_vertex_attribute_pointer(&vertex_buffer);
+        vertex_buffer.init_size(usage, self.format.stride() * size);
+        init_vertex_attribute_pointer(&self.format, &vertex_buffer);
         self.vertex_buffer = Some(vertex_buffer);
         self.vertex_size = Some(size);
         self
@@ -114,12 +133,23 @@ impl RendererBuilder {
         let vertex_buffer = Buffer::new_vertex(self.gl.clone()).unwrap();
         vertex_buffer.bind();
         vertex_buffer.init_with_data(usage, &convert_vertices_to_data(vertices));
-        init_vertex_attribute_pointer(&vertex_buffer);
+        init_vertex_attribute_pointer(&self.format, &vertex_buffer);
         self.vertex_buffer = Some(vertex_buffer);
         self.vertex_size = Some(vertices.len());
         self
     }
 
+    pub fn init_with_vertex_data(mut self, usage: BufferUsage, data: &[f32]) -> Self {
+        self.assert_vertex_buffer_not_init();
+        let vertex_buffer = Buffer::new_vertex(self.gl.clone()).unwrap();
+        vertex_buffer.bind();
+        vertex_buffer.init_with_data(usage, data);
+        init_vertex_attribute_pointer(&self.format, &vertex_buffer);
+        self.vertex_buffer = Some(vertex_buffer);
+        self.vertex_size = Some(data.len() / self.format.stride());
+        self
+    }
+
     fn assert_element_buffer_not_init(&self) {
         assert!(self.element_buffer.is_none(), "element buffer has been setup");
     }
@@ -134,7 +164,7 @@ impl RendererBuilder {
         self
     }
 
-    pub fn init_with_elements(mut self, usage: BufferUsage, elements: &[u16]) -> Self {
+    pub fn init_with_elements(mut self, usage: BufferUsage, elements: &[E]) -> Self {
         self.assert_element_buffer_not_init();
         let element_buffer = Buffer::new_element(self.gl.clone()).unwrap();
         element_buffer.bind();
@@ -144,7 +174,7 @@ impl RendererBuilder {
         self
     }
 
-    pub fn build(self) -> GameResult<Renderer> {
+    pub fn build(self) -> GameResult<Renderer<E>> {
         let vertex_array = self.vertex_array;
         let vertex_buffer = self.vertex_buffer
             .ok_or_else(|| GameError::InitError("must setup vertex buffer".into()))?;
@@ -158,6 +188,7 @@ impl RendererBuilder {
         vertex_buffer.unbind();
         element_buffer.unbind();
         Ok(Renderer {
+            format: self.format,
             vertex_array,
             vertex_buffer,
             vertex_size,
@@ -167,14 +198,18 @@ impl RendererBuilder {
     }
 }
 
-fn init_vertex_attribute_pointer(vertex_buffer: &VertexBuffer) {
-    vertex_buffer.set_attrib_pointer_f32(0, vertex::ATTRIBUTE_POSITION_SIZE, vertex::ATTRIBUTE_STRIDE, vertex::ATTRIBUTE_OFFSET_0);
-    vertex_buffer.set_attrib_pointer_f32(1, vertex::ATTRIBUTE_UV_SIZE, vertex::ATTRIBUTE_STRIDE, vertex::ATTRIBUTE_OFFSET_1);
-    vertex_buffer.set_attrib_pointer_f32(2, vertex::ATTRIBUTE_COLOR_SIZE, vertex::ATTRIBUTE_STRIDE, vertex::ATTRIBUTE_OFFSET_2);
+fn init_vertex_attribute_pointer(format: &VertexFormat, vertex_buffer: &VertexBuffer) {
+    for (index, attribute) in format.attributes().iter().enumerate() {
+        match attribute.attribute_type {
+            AttributeType::Float => vertex_buffer.set_attrib_pointer_f32(index, attribute.size, format.stride(), attribute.offset),
+            AttributeType::Int => vertex_buffer.set_attrib_pointer_i32(index, attribute.size, format.stride(), attribute.offset),
+            AttributeType::UnsignedInt => vertex_buffer.set_attrib_pointer_u32(index, attribute.size, format.stride(), attribute.offset),
+        }
+    }
 }
 
 fn convert_vertices_to_data(vertices: &[Vertex]) -> Vec<f32> {
-    let mut data = Vec::with_capacity(vertex::ATTRIBUTE_STRIDE * vertices.len());
+    let mut data = Vec::with_capacity(VertexFormat::standard().stride() * vertices.len());
     for vertex in vertices {
         data.push(vertex.position.x);
         data.push(vertex.position.y);
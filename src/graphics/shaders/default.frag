@@ -1,6 +1,7 @@
 #version 330 core
 
 uniform sampler2D u_texture;
+uniform float u_alpha_test;
 
 in vec2 v_uv;
 in vec4 v_color;
@@ -9,4 +10,7 @@ out vec4 frag_color;
 
 void main() {
     frag_color = texture(u_texture, v_uv) * v_color;
+    if (frag_color.a < u_alpha_test) {
+        discard;
+    }
 }
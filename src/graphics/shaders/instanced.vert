@@ -0,0 +1,24 @@
+#version 330 core
+
+uniform mat4 u_projection;
+
+layout (location = 0) in vec2 a_corner;
+layout (location = 1) in vec4 a_matrix;
+layout (location = 2) in vec2 a_translation;
+layout (location = 3) in vec4 a_rect;
+layout (location = 4) in vec4 a_uv_rect;
+layout (location = 5) in vec4 a_color_0;
+layout (location = 6) in vec4 a_color_1;
+layout (location = 7) in vec4 a_color_2;
+layout (location = 8) in vec4 a_color_3;
+
+out vec2 v_uv;
+out vec4 v_color;
+
+void main() {
+    vec2 position = mat2(a_matrix.xy, a_matrix.zw) * (a_rect.xy + a_corner * a_rect.zw) + a_translation;
+    int corner = int(a_corner.x) + int(a_corner.y) * 2;
+    v_uv = a_uv_rect.xy + a_corner * a_uv_rect.zw;
+    v_color = corner == 0 ? a_color_0 : corner == 1 ? a_color_1 : corner == 2 ? a_color_2 : a_color_3;
+    gl_Position = u_projection * vec4(position, 0.0, 1.0);
+}
@@ -0,0 +1,165 @@
+use super::{opengl, DrawCommand, Vertex, VertexData};
+use super::sprite_renderer::INSTANCE_STRIDE;
+use super::mesh::MeshBuffers;
+use glam::Mat4;
+use std::rc::Rc;
+
+/// How draws are submitted.
+///
+/// Both sorted modes record draws and sort them when the batch is flushed. Canvas, scissor and mask
+/// changes flush the recorded draws first, and so does a uniform or sampler change on a program that
+/// recorded draws use, on the next draw or program switch.
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum SortMode {
+    /// Draws in call order, layers are ignored.
+    #[default]
+    Immediate,
+    /// Sorts by layer only, draws in the same layer keep their call order.
+    Layered,
+    /// Sorts by (layer, program, texture), draws in the same layer may be reordered.
+    Deferred,
+}
+
+pub(crate) enum DeferredGeometry {
+    Vertices(Vec<Vertex>, Vec<u32>),
+    Instance([f32; INSTANCE_STRIDE]),
+    VertexData(VertexData, Vec<u32>),
+    /// The matrix is the projection combined with the transform at the time of the draw.
+    StaticMesh(MeshBuffers, Mat4),
+}
+
+pub(crate) struct DeferredDraw {
+    pub layer: i32,
+    pub program: Rc<opengl::Program>,
+    pub command: DrawCommand,
+    pub geometry: DeferredGeometry,
+}
+
+/// Draws recorded in sorted modes and the programs they are recorded with.
+pub(crate) struct Recording<P, D> {
+    draws: Vec<D>,
+    programs: Vec<Rc<P>>,
+}
+
+impl<P: Uniforms, D> Recording<P, D> {
+    pub fn new() -> Self {
+        Self {
+            draws: Vec::new(),
+            programs: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    pub fn record(&mut self, program: &Rc<P>, draw: D) {
+        if !self.programs.iter().any(|recorded| Rc::ptr_eq(recorded, program)) {
+            self.programs.push(program.clone());
+        }
+        self.draws.push(draw);
+    }
+
+    /// Whether uniforms or samplers of a program used by the recorded draws are changed since they are recorded,
+    /// the draws have to be submitted before the change is applied.
+    pub fn is_stale(&self) -> bool {
+        self.programs.iter().any(|program| program.has_dirty_uniforms())
+    }
+
+    /// Takes the recorded draws sorted by `key`, stable, so draws with equal keys keep their call order.
+    pub fn take_sorted<K: Ord>(&mut self, key: impl FnMut(&D) -> K) -> Vec<D> {
+        self.programs.clear();
+        let mut draws = std::mem::take(&mut self.draws);
+        draws.sort_by_key(key);
+        draws
+    }
+}
+
+pub(crate) trait Uniforms {
+    fn has_dirty_uniforms(&self) -> bool;
+}
+
+impl Uniforms for opengl::Program {
+    fn has_dirty_uniforms(&self) -> bool {
+        opengl::Program::has_dirty_uniforms(self)
+    }
+}
+
+impl DeferredDraw {
+    pub fn sort_key(&self, sort_mode: SortMode) -> (i32, usize, usize) {
+        sort_key(sort_mode, self.layer, Rc::as_ptr(&self.program) as usize, Rc::as_ptr(&self.command.texture) as usize)
+    }
+}
+
+fn sort_key(sort_mode: SortMode, layer: i32, program: usize, texture: usize) -> (i32, usize, usize) {
+    match sort_mode {
+        SortMode::Deferred => (layer, program, texture),
+        _ => (layer, 0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SortMode, Recording, Uniforms, sort_key};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct TestProgram {
+        dirty: Cell<bool>,
+    }
+
+    impl Uniforms for TestProgram {
+        fn has_dirty_uniforms(&self) -> bool {
+            self.dirty.get()
+        }
+    }
+
+    fn sort(sort_mode: SortMode, draws: &[(i32, usize, usize)]) -> Vec<usize> {
+        let mut indices = (0..draws.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|index| {
+            let (layer, program, texture) = draws[*index];
+            sort_key(sort_mode, layer, program, texture)
+        });
+        indices
+    }
+
+    #[test]
+    fn test_sort_key() {
+        assert_eq!(sort_key(SortMode::Layered, 2, 1, 1), (2, 0, 0));
+        assert_eq!(sort_key(SortMode::Deferred, 2, 1, 3), (2, 1, 3));
+        let draws = [(1, 2, 1), (0, 2, 2), (1, 1, 2), (0, 1, 1), (1, 2, 1), (0, 2, 1)];
+        assert_eq!(sort(SortMode::Layered, &draws), vec![1, 3, 5, 0, 2, 4]);
+        assert_eq!(sort(SortMode::Deferred, &draws), vec![3, 5, 1, 2, 0, 4]);
+    }
+
+    #[test]
+    fn test_layer_order() {
+        let draws = [(0, 1, 1), (i32::MAX, 1, 1), (-1, 2, 2), (i32::MIN, 2, 1), (0, 1, 2), (-1, 1, 1)];
+        assert_eq!(sort(SortMode::Layered, &draws), vec![3, 2, 5, 0, 4, 1]);
+        assert_eq!(sort(SortMode::Deferred, &draws), vec![3, 5, 2, 0, 4, 1]);
+        assert_eq!(sort(SortMode::Layered, &[(0, 2, 2), (0, 1, 1), (0, 2, 1)]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_recording() {
+        let program = Rc::new(TestProgram { dirty: Cell::new(false) });
+        let default_program = Rc::new(TestProgram { dirty: Cell::new(false) });
+        let mut recording = Recording::new();
+        recording.record(&program, (1, 'a'));
+        recording.record(&default_program, (0, 'b'));
+        recording.record(&program, (0, 'c'));
+        assert!(!recording.is_stale());
+
+        // Changed after the draws are recorded, while another program is in use.
+        program.dirty.set(true);
+        assert!(recording.is_stale());
+        assert_eq!(recording.take_sorted(|draw| draw.0), vec![(0, 'b'), (0, 'c'), (1, 'a')]);
+        assert!(recording.is_empty());
+        assert!(!recording.is_stale());
+
+        recording.record(&default_program, (0, 'd'));
+        assert!(!recording.is_stale());
+        default_program.dirty.set(true);
+        assert!(recording.is_stale());
+    }
+}
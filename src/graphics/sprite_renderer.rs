@@ -0,0 +1,95 @@
+use super::opengl::{VertexArray, BufferUsage, Buffer, VertexBuffer, ElementType, ElementBuffer, PrimitiveType};
+use crate::error::{GameError, GameResult};
+use glow::Context;
+use std::rc::Rc;
+
+pub const INSTANCE_MATRIX_SIZE: usize = 4;
+pub const INSTANCE_TRANSLATION_SIZE: usize = 2;
+pub const INSTANCE_RECT_SIZE: usize = 4;
+pub const INSTANCE_UV_RECT_SIZE: usize = 4;
+pub const INSTANCE_COLOR_SIZE: usize = 4;
+pub const INSTANCE_COLOR_COUNT: usize = 4;
+pub const INSTANCE_STRIDE: usize = INSTANCE_MATRIX_SIZE + INSTANCE_TRANSLATION_SIZE + INSTANCE_RECT_SIZE + INSTANCE_UV_RECT_SIZE + INSTANCE_COLOR_SIZE * INSTANCE_COLOR_COUNT;
+
+const CORNER_SIZE: usize = 2;
+const CORNERS: [f32; CORNER_SIZE * 4] = [
+    0.0, 0.0,
+    1.0, 0.0,
+    0.0, 1.0,
+    1.0, 1.0,
+];
+
+/// Draws sprites as instances of one quad, the vertex shader expands each instance from its transform, rect, uv rect and corner colors.
+pub struct SpriteRenderer {
+    vertex_array: VertexArray,
+    // Only referenced through the vertex array, kept so the buffers live as long as it.
+    _corner_buffer: VertexBuffer,
+    _element_buffer: ElementBuffer,
+    element_count: usize,
+    instance_buffer: VertexBuffer,
+    instance_size: usize,
+}
+
+impl SpriteRenderer {
+    pub fn new(gl: Rc<Context>, elements: &[u16], instance_size: usize) -> GameResult<Self> {
+        let vertex_array = VertexArray::new(gl.clone())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        let corner_buffer = Buffer::new_vertex(gl.clone())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        let element_buffer = Buffer::new_element(gl.clone())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        let instance_buffer = Buffer::new_vertex(gl)
+            .map_err(|error| GameError::InitError(error.into()))?;
+
+        vertex_array.bind();
+
+        corner_buffer.bind();
+        corner_buffer.init_with_data(BufferUsage::Static, &CORNERS);
+        corner_buffer.set_attrib_pointer_f32(0, CORNER_SIZE, CORNER_SIZE, 0);
+
+        element_buffer.bind();
+        element_buffer.init_with_data(BufferUsage::Static, elements);
+
+        instance_buffer.bind();
+        instance_buffer.init_size(BufferUsage::Stream, INSTANCE_STRIDE * instance_size);
+        let mut offset = 0;
+        let attribute_sizes = [INSTANCE_MATRIX_SIZE, INSTANCE_TRANSLATION_SIZE, INSTANCE_RECT_SIZE, INSTANCE_UV_RECT_SIZE]
+            .into_iter()
+            .chain([INSTANCE_COLOR_SIZE; INSTANCE_COLOR_COUNT]);
+        for (index, size) in attribute_sizes.enumerate() {
+            instance_buffer.set_attrib_pointer_f32(index + 1, size, INSTANCE_STRIDE, offset);
+            instance_buffer.set_attrib_divisor(index + 1, 1);
+            offset += size;
+        }
+
+        vertex_array.unbind();
+        corner_buffer.unbind();
+        element_buffer.unbind();
+        instance_buffer.unbind();
+
+        Ok(Self {
+            vertex_array,
+            _corner_buffer: corner_buffer,
+            _element_buffer: element_buffer,
+            element_count: elements.len(),
+            instance_buffer,
+            instance_size,
+        })
+    }
+
+    pub fn update_instances(&self, offset: usize, data: &[f32]) {
+        self.instance_buffer.bind();
+        self.instance_buffer.sub_data(offset, data);
+        self.instance_buffer.unbind();
+    }
+
+    pub fn instance_size(&self) -> usize {
+        self.instance_size
+    }
+
+    pub fn draw_instances(&self, instance_count: usize) {
+        self.vertex_array.bind();
+        self.vertex_array.draw_elements_instanced(PrimitiveType::Triangles, ElementType::UnsignedShort, self.element_count, 0, instance_count);
+        self.vertex_array.unbind();
+    }
+}
@@ -0,0 +1,44 @@
+/// Counters for one frame, reset on each present.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DrawStats {
+    /// Every draw call submitted to OpenGL, including static and custom format meshes.
+    pub draw_calls: usize,
+    pub vertices: usize,
+    pub elements: usize,
+    pub instances: usize,
+    /// Changes of the draw texture between draw calls and of sampler textures bound to the current program.
+    pub texture_switches: usize,
+    pub primitive_switches: usize,
+    pub blend_mode_switches: usize,
+    pub program_switches: usize,
+    pub canvas_switches: usize,
+    /// Flushes forced by changing uniforms of the current program.
+    pub uniform_flushes: usize,
+    /// Flushes forced by a full renderer buffer.
+    pub capacity_flushes: usize,
+}
+
+impl DrawStats {
+    /// Returns the counters of the finished frame and starts the next one from zero.
+    pub(crate) fn finish_frame(&mut self) -> Self {
+        std::mem::take(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DrawStats;
+
+    #[test]
+    fn test_finish_frame() {
+        let mut frame_stats = DrawStats::default();
+        frame_stats.draw_calls += 2;
+        frame_stats.texture_switches += 1;
+        let stats = frame_stats.finish_frame();
+        assert_eq!(stats.draw_calls, 2);
+        assert_eq!(stats.texture_switches, 1);
+        assert_eq!(frame_stats, DrawStats::default());
+        frame_stats.draw_calls += 1;
+        assert_eq!(frame_stats.finish_frame().draw_calls, 1);
+    }
+}
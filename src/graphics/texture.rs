@@ -2,15 +2,14 @@ use super::{Graphics, opengl, FilterMode, Filter, WrapMode, Wrap, Image, validat
 use crate::error::{GameError, GameResult};
 use crate::math::{Size, Region};
 use crate::engine::Engine;
+use crate::filesystem::Reload;
 use glow::Context;
-use std::rc::Rc;
-use std::path::Path;
+use std::rc::{Rc, Weak};
+use std::path::{Path, PathBuf};
 
 pub struct Texture {
     texture: Rc<opengl::Texture>,
-    size: Size<u32>,
     filter: Filter,
-    mipmap_generated: bool,
     wrap: Wrap,
 }
 
@@ -35,9 +34,7 @@ impl Texture {
         texture.unbind();
         Ok(Self {
             texture: Rc::new(texture),
-            size,
             filter,
-            mipmap_generated: generate_mipmap,
             wrap,
         })
     }
@@ -54,8 +51,18 @@ impl Texture {
     }
 
     pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
         let image = Image::load(engine, path)?;
-        Self::from_image(engine.graphics(), &image)
+        let texture = Self::from_image(engine.graphics(), &image)?;
+        texture.watch(engine, path);
+        Ok(texture)
+    }
+
+    pub(crate) fn watch(&self, engine: &mut Engine, path: &Path) {
+        engine.watch_asset(vec![path.to_owned()], TextureReload {
+            texture: Rc::downgrade(&self.texture),
+            path: path.to_owned(),
+        });
     }
 
     pub(crate) fn white_1_1(gl: Rc<Context>) -> GameResult<Rc<opengl::Texture>> {
@@ -86,9 +93,7 @@ impl Texture {
         texture.unbind();
         Ok(Self {
             texture: Rc::new(texture),
-            size,
             filter,
-            mipmap_generated: generate_mipmap,
             wrap,
         })
     }
@@ -98,7 +103,7 @@ impl Texture {
     }
 
     pub fn size(&self) -> Size<u32> {
-        self.size
+        self.texture.size()
     }
 
     pub fn filter(&self) -> Filter {
@@ -109,9 +114,8 @@ impl Texture {
         if self.filter != filter {
             self.texture.bind();
             self.texture.set_filter(filter);
-            if !self.mipmap_generated && filter.mipmap.is_some() {
+            if !self.texture.is_mipmap_generated() && filter.mipmap.is_some() {
                 self.texture.generate_mipmap();
-                self.mipmap_generated = true;
             }
             self.texture.unbind();
             self.filter = filter;
@@ -138,12 +142,8 @@ impl Texture {
         }
         self.texture.bind();
         self.texture.init_image(size.width, size.height, pixels);
-        self.size = size;
         if self.filter.mipmap.is_some() {
             self.texture.generate_mipmap();
-            self.mipmap_generated = true;
-        } else {
-            self.mipmap_generated = false;
         }
         self.texture.unbind();
         Ok(())
@@ -170,9 +170,6 @@ impl Texture {
         );
         if self.filter.mipmap.is_some() {
             self.texture.generate_mipmap();
-            self.mipmap_generated = true;
-        } else {
-            self.mipmap_generated = false;
         }
         self.texture.unbind();
         Ok(())
@@ -182,13 +179,34 @@ impl Texture {
         let size = size.into();
         self.texture.bind();
         self.texture.init_image(size.width, size.height, None);
-        self.size = size;
         if self.filter.mipmap.is_some() {
             self.texture.generate_mipmap();
-            self.mipmap_generated = true;
-        } else {
-            self.mipmap_generated = false;
         }
         self.texture.unbind();
     }
 }
+
+struct TextureReload {
+    texture: Weak<opengl::Texture>,
+    path: PathBuf,
+}
+
+impl Reload for TextureReload {
+    fn is_alive(&self) -> bool {
+        self.texture.strong_count() > 0
+    }
+
+    fn reload(&self, engine: &mut Engine) -> GameResult {
+        let image = Image::load(engine, &self.path)?;
+        if let Some(texture) = self.texture.upgrade() {
+            let size = image.size();
+            texture.bind();
+            texture.init_image(size.width, size.height, Some(image.pixels()));
+            if texture.is_mipmap_filter() {
+                texture.generate_mipmap();
+            }
+            texture.unbind();
+        }
+        Ok(())
+    }
+}
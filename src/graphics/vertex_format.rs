@@ -0,0 +1,164 @@
+use super::{vertex, Vertex};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum AttributeType {
+    Float,
+    Int,
+    UnsignedInt,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct VertexAttribute {
+    pub attribute_type: AttributeType,
+    pub size: usize,
+    pub offset: usize,
+}
+
+/// Attributes are bound to locations in the order they are added, every component takes 4 bytes.
+#[derive(Debug, Default, Clone, Hash, Eq, PartialEq)]
+pub struct VertexFormat {
+    attributes: Vec<VertexAttribute>,
+    stride: usize,
+}
+
+impl VertexFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The layout of `Vertex`: position, uv and color.
+    pub fn standard() -> Self {
+        Self::new()
+            .float(vertex::ATTRIBUTE_POSITION_SIZE)
+            .float(vertex::ATTRIBUTE_UV_SIZE)
+            .float(vertex::ATTRIBUTE_COLOR_SIZE)
+    }
+
+    pub fn attribute(mut self, attribute_type: AttributeType, size: usize) -> Self {
+        assert!((1..=4).contains(&size), "attribute size must be in 1..=4");
+        self.attributes.push(VertexAttribute {
+            attribute_type,
+            size,
+            offset: self.stride,
+        });
+        self.stride += size;
+        self
+    }
+
+    pub fn float(self, size: usize) -> Self {
+        self.attribute(AttributeType::Float, size)
+    }
+
+    pub fn int(self, size: usize) -> Self {
+        self.attribute(AttributeType::Int, size)
+    }
+
+    pub fn unsigned_int(self, size: usize) -> Self {
+        self.attribute(AttributeType::UnsignedInt, size)
+    }
+
+    pub fn attributes(&self) -> &[VertexAttribute] {
+        &self.attributes
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub(crate) fn has_position(&self) -> bool {
+        self.attributes.first()
+            .is_some_and(|attribute| attribute.attribute_type == AttributeType::Float && attribute.size >= 2)
+    }
+}
+
+/// Vertices in a custom `VertexFormat`. Components are pushed in attribute order.
+///
+/// If the first attribute is a float vector, it is treated as the position and transformed when drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexData {
+    format: VertexFormat,
+    data: Vec<f32>,
+}
+
+impl VertexData {
+    pub fn new(format: VertexFormat) -> Self {
+        Self {
+            format,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn format(&self) -> &VertexFormat {
+        &self.format
+    }
+
+    pub fn push_f32(&mut self, values: &[f32]) -> &mut Self {
+        self.data.extend_from_slice(values);
+        self
+    }
+
+    pub fn push_i32(&mut self, values: &[i32]) -> &mut Self {
+        self.data.extend(values.iter().map(|value| f32::from_bits(*value as u32)));
+        self
+    }
+
+    pub fn push_u32(&mut self, values: &[u32]) -> &mut Self {
+        self.data.extend(values.iter().map(|value| f32::from_bits(*value)));
+        self
+    }
+
+    pub fn push_vertex(&mut self, vertex: Vertex) -> &mut Self {
+        self.push_f32(&[
+            vertex.position.x,
+            vertex.position.y,
+            vertex.uv.x,
+            vertex.uv.y,
+            vertex.color.red,
+            vertex.color.green,
+            vertex.color.blue,
+            vertex.color.alpha,
+        ])
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len().checked_div(self.format.stride).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub(crate) fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    pub(crate) fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributeType, VertexFormat, VertexData};
+
+    #[test]
+    fn test_format() {
+        let format = VertexFormat::new().float(2).float(3).int(1);
+        assert_eq!(format.stride(), 6);
+        assert_eq!(format.attributes()[1].offset, 2);
+        assert_eq!(format.attributes()[2].attribute_type, AttributeType::Int);
+        assert!(format.has_position());
+        assert!(!VertexFormat::new().int(2).has_position());
+        assert_eq!(VertexFormat::standard().stride(), 8);
+
+        let mut vertex_data = VertexData::new(format);
+        vertex_data.push_f32(&[0.0, 0.0, 1.0, 0.0, 0.0]).push_i32(&[-1]);
+        vertex_data.push_f32(&[1.0, 1.0, 0.0, 1.0, 0.0]).push_i32(&[2]);
+        assert_eq!(vertex_data.len(), 2);
+        assert_eq!(vertex_data.data()[5].to_bits() as i32, -1);
+    }
+}
@@ -12,5 +12,7 @@ pub mod touch;
 pub mod touchpad;
 pub mod gamepad;
 pub mod audio;
+pub mod loader;
+pub mod asset;
 pub mod game;
 pub mod prelude;
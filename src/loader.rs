@@ -0,0 +1,375 @@
+use crate::error::{GameError, GameResult};
+use crate::engine::Engine;
+use crate::filesystem::Filesystem;
+use crate::graphics::{Image, Texture, Font, Program, parse_font};
+use crate::audio::{Sound, Music};
+use std::any::Any;
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Sender, Receiver};
+use std::thread::{self, JoinHandle};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum LoadState {
+    Pending,
+    Ready,
+    Failed,
+}
+
+enum Slot<T> {
+    Pending,
+    Ready(T),
+    Failed(GameError),
+}
+
+pub struct LoadHandle<T> {
+    slot: Rc<RefCell<Slot<T>>>,
+}
+
+impl<T> LoadHandle<T> {
+    pub fn state(&self) -> LoadState {
+        match &*self.slot.borrow() {
+            Slot::Pending => LoadState::Pending,
+            Slot::Ready(_) => LoadState::Ready,
+            Slot::Failed(_) => LoadState::Failed,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.state() == LoadState::Ready
+    }
+
+    pub fn get(&self) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.slot.borrow(), |slot| match slot {
+            Slot::Ready(asset) => Some(asset),
+            _ => None,
+        }).ok()
+    }
+
+    pub fn get_mut(&self) -> Option<RefMut<'_, T>> {
+        RefMut::filter_map(self.slot.borrow_mut(), |slot| match slot {
+            Slot::Ready(asset) => Some(asset),
+            _ => None,
+        }).ok()
+    }
+
+    pub fn error(&self) -> Option<Ref<'_, GameError>> {
+        Ref::filter_map(self.slot.borrow(), |slot| match slot {
+            Slot::Failed(error) => Some(error),
+            _ => None,
+        }).ok()
+    }
+}
+
+impl<T> Clone for LoadHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+// `GameError` holds non-`Send` sources, so errors cross the worker boundary as the variant and its message.
+type ErrorVariant = fn(Box<dyn Error>) -> GameError;
+
+struct WorkerError {
+    variant: ErrorVariant,
+    message: String,
+}
+
+impl From<GameError> for WorkerError {
+    fn from(error: GameError) -> Self {
+        let (variant, source): (ErrorVariant, _) = match error {
+            GameError::IoError(source) => (GameError::IoError, source),
+            GameError::InitError(source) => (GameError::InitError, source),
+            GameError::StateError(source) => (GameError::StateError, source),
+            GameError::RuntimeError(source) => (GameError::RuntimeError, source),
+            GameError::NotSupportedError(source) => (GameError::NotSupportedError, source),
+        };
+        Self {
+            variant,
+            message: source.to_string(),
+        }
+    }
+}
+
+impl From<WorkerError> for GameError {
+    fn from(error: WorkerError) -> Self {
+        (error.variant)(error.message.into())
+    }
+}
+
+type Decoded = Result<Box<dyn Any + Send>, WorkerError>;
+type Job = (u64, Box<dyn FnOnce(&Filesystem) -> Decoded + Send>);
+type Finish = Box<dyn FnOnce(&mut Engine, GameResult<Box<dyn Any + Send>>)>;
+type Finished = Box<dyn FnOnce(&mut Engine)>;
+
+pub struct Loader {
+    job_sender: Option<Sender<Job>>,
+    result_receiver: Receiver<(u64, Decoded)>,
+    workers: Vec<JoinHandle<()>>,
+    finishes: HashMap<u64, Finish>,
+    next_id: u64,
+    total_count: usize,
+    finished_count: usize,
+}
+
+impl Loader {
+    pub(crate) fn new(loader_config: LoaderConfig, filesystem: Filesystem) -> GameResult<Self> {
+        let (job_sender, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let worker_count = loader_config.worker_count.max(1);
+        let mut workers = Vec::with_capacity(worker_count);
+        for index in 0..worker_count {
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+            let filesystem = filesystem.clone();
+            let worker = thread::Builder::new()
+                .name(format!("tge-loader-{}", index))
+                .spawn(move || loop {
+                    let job = job_receiver.lock().unwrap_or_else(|error| error.into_inner()).recv();
+                    match job {
+                        Ok((id, decode)) => {
+                            if result_sender.send((id, decode(&filesystem))).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                })
+                .map_err(|error| GameError::InitError(error.into()))?;
+            workers.push(worker);
+        }
+        Ok(Self {
+            job_sender: Some(job_sender),
+            result_receiver,
+            workers,
+            finishes: HashMap::new(),
+            next_id: 0,
+            total_count: 0,
+            finished_count: 0,
+        })
+    }
+
+    fn load<D, T>(
+        &mut self,
+        decode: impl FnOnce(&Filesystem) -> GameResult<D> + Send + 'static,
+        finish: impl FnOnce(&mut Engine, D) -> GameResult<T> + 'static,
+    ) -> LoadHandle<T>
+        where
+            D: Send + 'static,
+            T: 'static,
+    {
+        if self.finished_count == self.total_count {
+            self.total_count = 0;
+            self.finished_count = 0;
+        }
+        let handle = LoadHandle {
+            slot: Rc::new(RefCell::new(Slot::Pending)),
+        };
+        let id = self.next_id;
+        self.next_id += 1;
+        let job: Job = (id, Box::new(move |filesystem| {
+            decode(filesystem)
+                .map(|decoded| Box::new(decoded) as Box<dyn Any + Send>)
+                .map_err(WorkerError::from)
+        }));
+        let job_sent = self.job_sender.as_ref()
+            .is_some_and(|job_sender| job_sender.send(job).is_ok());
+        if job_sent {
+            let slot = handle.slot.clone();
+            self.finishes.insert(id, Box::new(move |engine, decoded| {
+                let result = decoded.and_then(|decoded| {
+                    let decoded = decoded.downcast::<D>()
+                        .map_err(|_| GameError::RuntimeError("loader decoded type mismatch".into()))?;
+                    finish(engine, *decoded)
+                });
+                *slot.borrow_mut() = match result {
+                    Ok(asset) => Slot::Ready(asset),
+                    Err(error) => Slot::Failed(error),
+                };
+            }));
+            self.total_count += 1;
+        } else {
+            *handle.slot.borrow_mut() = Slot::Failed(GameError::StateError("loader workers are not running".into()));
+        }
+        handle
+    }
+
+    pub fn load_bytes(&mut self, path: impl AsRef<Path>) -> LoadHandle<Vec<u8>> {
+        let path = path.as_ref().to_owned();
+        self.load(move |filesystem| filesystem.read(path), |_, bytes| Ok(bytes))
+    }
+
+    pub fn load_image(&mut self, path: impl AsRef<Path>) -> LoadHandle<Image> {
+        let path = path.as_ref().to_owned();
+        self.load(move |filesystem| Image::from_bytes(&filesystem.read(path)?), |_, image| Ok(image))
+    }
+
+    pub fn load_texture(&mut self, path: impl AsRef<Path>) -> LoadHandle<Texture> {
+        let path = path.as_ref().to_owned();
+        let decode_path = path.clone();
+        self.load(
+            move |filesystem| Image::from_bytes(&filesystem.read(decode_path)?),
+            move |engine, image| {
+                let texture = Texture::from_image(engine.graphics(), &image)?;
+                texture.watch(engine, &path);
+                Ok(texture)
+            },
+        )
+    }
+
+    pub fn load_font(&mut self, path: impl AsRef<Path>) -> LoadHandle<Font> {
+        let path = path.as_ref().to_owned();
+        let decode_path = path.clone();
+        self.load(
+            move |filesystem| parse_font(&filesystem.read(decode_path)?),
+            move |engine, font| {
+                let font = Font::from_font(engine.graphics(), font)?;
+                font.watch(engine, &path);
+                Ok(font)
+            },
+        )
+    }
+
+    pub fn load_program(&mut self, vertex_shader_path: impl AsRef<Path>, fragment_shader_path: impl AsRef<Path>) -> LoadHandle<Program> {
+        let vertex_shader_path = vertex_shader_path.as_ref().to_owned();
+        let fragment_shader_path = fragment_shader_path.as_ref().to_owned();
+        let decode_paths = (vertex_shader_path.clone(), fragment_shader_path.clone());
+        self.load(
+            move |filesystem| {
+                let vertex_shader_source = filesystem.read_to_string(decode_paths.0)?;
+                let fragment_shader_source = filesystem.read_to_string(decode_paths.1)?;
+                Ok((vertex_shader_source, fragment_shader_source))
+            },
+            move |engine, (vertex_shader_source, fragment_shader_source)| {
+                let program = Program::new(engine.graphics(), vertex_shader_source, fragment_shader_source)?;
+                program.watch(engine, &vertex_shader_path, &fragment_shader_path);
+                Ok(program)
+            },
+        )
+    }
+
+    pub fn load_sound(&mut self, path: impl AsRef<Path>) -> LoadHandle<Sound> {
+        let path = path.as_ref().to_owned();
+        let decode_path = path.clone();
+        self.load(
+            move |filesystem| Sound::from_bytes(&filesystem.read(decode_path)?),
+            move |engine, sound| {
+                sound.watch(engine, &path);
+                Ok(sound)
+            },
+        )
+    }
+
+    pub fn load_music(&mut self, path: impl AsRef<Path>) -> LoadHandle<Music> {
+        let path = path.as_ref().to_owned();
+        let decode_path = path.clone();
+        self.load(
+            move |filesystem| Music::from_bytes(&filesystem.read(decode_path)?),
+            move |engine, music| {
+                music.watch(engine, &path);
+                Ok(music)
+            },
+        )
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.total_count - self.finished_count
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.total_count == 0 {
+            1.0
+        } else {
+            self.finished_count as f32 / self.total_count as f32
+        }
+    }
+
+    pub(crate) fn take_finished(&mut self) -> Vec<Finished> {
+        let mut finished: Vec<Finished> = Vec::new();
+        while let Ok((id, decoded)) = self.result_receiver.try_recv() {
+            if let Some(finish) = self.finishes.remove(&id) {
+                self.finished_count += 1;
+                let decoded = decoded.map_err(GameError::from);
+                finished.push(Box::new(move |engine| finish(engine, decoded)));
+            }
+        }
+        finished
+    }
+}
+
+impl Drop for Loader {
+    fn drop(&mut self) {
+        self.job_sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoaderConfig {
+    worker_count: usize,
+}
+
+impl LoaderConfig {
+    pub fn new() -> Self {
+        Self {
+            worker_count: 2,
+        }
+    }
+
+    pub fn worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Loader, LoaderConfig, LoadState, WorkerError};
+    use crate::error::GameError;
+    use crate::filesystem::{Filesystem, FilesystemConfig, Mount};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_progress() {
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .mount(Mount::memory([("a.txt", "a")]))
+        ).unwrap();
+        let mut loader = Loader::new(LoaderConfig::new(), filesystem).unwrap();
+        assert_eq!(loader.progress(), 1.0);
+        let handle = loader.load_bytes("a.txt");
+        loader.load_bytes("b.txt");
+        assert_eq!(loader.pending_count(), 2);
+        assert_eq!(loader.progress(), 0.0);
+        assert_eq!(handle.state(), LoadState::Pending);
+        let mut finished = 0;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while finished < 2 {
+            assert!(Instant::now() < deadline, "loads did not finish in time");
+            finished += loader.take_finished().len();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(loader.pending_count(), 0);
+        assert_eq!(loader.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_worker_error() {
+        let error = GameError::from(WorkerError::from(GameError::IoError("not found".into())));
+        assert!(matches!(&error, GameError::IoError(source) if source.to_string() == "not found"));
+    }
+}
@@ -154,6 +154,22 @@ impl<N: Number> Region<N> {
     pub fn bottom_right(&self) -> Position<N> {
         Position::new(self.x + self.width, self.y + self.height)
     }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let left = partial_max(self.left(), other.left());
+        let top = partial_max(self.top(), other.top());
+        let right = partial_max(left, partial_min(self.right(), other.right()));
+        let bottom = partial_max(top, partial_min(self.bottom(), other.bottom()));
+        Self::edge(left, right, top, bottom)
+    }
+}
+
+fn partial_min<N: Number>(a: N, b: N) -> N {
+    if b < a { b } else { a }
+}
+
+fn partial_max<N: Number>(a: N, b: N) -> N {
+    if b > a { b } else { a }
 }
 
 impl<N: Number> From<(N, N, N, N)> for Region<N> {
@@ -200,4 +216,12 @@ mod tests {
         assert_eq!(region.min(), region.top_left());
         assert_eq!(region.max(), region.bottom_right());
     }
+
+    #[test]
+    fn test_intersection() {
+        let region = Region::<f32>::new(10.0, 20.0, 100.0, 150.0);
+        assert_eq!(region.intersection(&Region::new(50.0, 0.0, 100.0, 100.0)), Region::new(50.0, 20.0, 60.0, 80.0));
+        assert_eq!(region.intersection(&Region::new(0.0, 0.0, 500.0, 500.0)), region);
+        assert_eq!(region.intersection(&Region::new(200.0, 0.0, 10.0, 10.0)).size(), Size::<f32>::new(0.0, 0.0));
+    }
 }
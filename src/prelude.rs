@@ -2,14 +2,16 @@ pub use crate::error::{GameError, GameResult};
 pub use crate::math::{Vector, Position, Size, Region, Viewport, Angle, Transform};
 pub use crate::engine::{Engine, EngineBuilder};
 pub use crate::event::{KeyAction, TouchPhase, Event};
-pub use crate::filesystem::{Filesystem, FilesystemConfig};
+pub use crate::filesystem::{Filesystem, FilesystemConfig, Mount};
 pub use crate::window::{Window, WindowConfig, Icon, LogicalPosition, PhysicalPosition, LogicalSize, PhysicalSize, FullscreenMode};
-pub use crate::graphics::{Graphics, GraphicsConfig, PrimitiveType, FilterMode, Filter, WrapMode, Wrap, Program, Color, Vertex, Image, Texture, Canvas, Font, TextureRef, MeshDrawParams, SpriteDrawParams, TextLayoutGravity, TextDrawParams};
+pub use crate::graphics::{Graphics, GraphicsConfig, PrimitiveType, FilterMode, Filter, WrapMode, Wrap, BlendFactor, BlendEquation, BlendComponent, BlendMode, UniformValue, Program, Color, Vertex, AttributeType, VertexAttribute, VertexFormat, VertexData, Elements, Image, Texture, Canvas, Font, TextureRef, MeshDrawParams, StaticMeshDrawParams, SpriteDrawParams, TextLayoutGravity, TextDrawParams, MaskMode, Mesh, DrawStats, SortMode};
 pub use crate::timer::{Timer, TimerConfig};
 pub use crate::keyboard::{Keyboard, KeyboardConfig, KeyCode, ModifiersState};
 pub use crate::mouse::{Mouse, MouseConfig, CursorIcon, MouseButton};
 pub use crate::touch::{Touch, TouchConfig};
 pub use crate::touchpad::{Touchpad, TouchpadConfig};
 pub use crate::gamepad::{Gamepad, GamepadConfig, GamepadButton, GamepadAxis, GamepadId, GamepadDevice, PowerInfo};
-pub use crate::audio::{Audio, AudioConfig};
+pub use crate::audio::{Audio, AudioConfig, AudioBackend, Sound, Music, SoundRef, SoundState, SoundInstance, DistanceModel, Attenuation};
+pub use crate::loader::{Loader, LoaderConfig, LoadState, LoadHandle};
+pub use crate::asset::Assets;
 pub use crate::game::Game;
@@ -63,6 +63,7 @@ impl Window {
             window_builder = window_builder.with_max_inner_size(winit::dpi::LogicalSize::new(size.width, size.height));
         }
         let context_builder = ContextBuilder::new()
+            .with_stencil_buffer(8)
             .with_vsync(window_config.vsync);
         let windowed_context = context_builder.build_windowed(window_builder, event_loop)
             .map_err(|error| GameError::InitError(error.into()))?;